@@ -11,43 +11,55 @@ impl ::syn::parse::Parse for EnumWithMixedVariants {
         input: &::syn::parse::ParseBuffer,
     ) -> ::std::result::Result<Self, ::syn::Error> {
         use ::syn::parse::discouraged::Speculative;
-        let fork = input.fork();
-        if let Ok(variant) = (|| {
-            Ok(EnumWithMixedVariants::TwoExpressionsSeparatedByKeyword {
-                first: fork.parse()?,
-                _the_dude: fork.parse()?,
-                second: fork.parse()?,
-            }) as ::std::result::Result<EnumWithMixedVariants, ::syn::Error>
-        })() {
-            input.advance_to(&fork);
-            return Ok(variant);
-        }
-        let fork = input.fork();
-        if let Ok(variant) = (|| {
-            Ok(
-                EnumWithMixedVariants::IdentifierPlusPlus(
-                    fork.parse()?,
-                    fork.parse()?,
-                    fork.parse()?,
+        let warnings_mark = ::parse_variants::__private::warnings_mark();
+        let result = (|| -> ::std::result::Result<Self, ::syn::Error> {
+            let fork = input.fork();
+            let warnings_mark = ::parse_variants::__private::warnings_mark();
+            if let Ok(variant) = (|| {
+                let field_0 = fork.parse()?;
+                let field_1 = fork.parse()?;
+                let field_2 = fork.parse()?;
+                Ok(EnumWithMixedVariants::TwoExpressionsSeparatedByKeyword {
+                    first: field_0,
+                    _the_dude: field_1,
+                    second: field_2,
+                }) as ::std::result::Result<EnumWithMixedVariants, ::syn::Error>
+            })() {
+                input.advance_to(&fork);
+                return Ok(variant);
+            }
+            ::parse_variants::__private::discard_warnings(warnings_mark);
+            let fork = input.fork();
+            let warnings_mark = ::parse_variants::__private::warnings_mark();
+            if let Ok(variant) = (|| {
+                let field_0 = fork.parse()?;
+                let field_1 = fork.parse()?;
+                let field_2 = fork.parse()?;
+                Ok(EnumWithMixedVariants::IdentifierPlusPlus(field_0, field_1, field_2))
+                    as ::std::result::Result<EnumWithMixedVariants, ::syn::Error>
+            })() {
+                input.advance_to(&fork);
+                return Ok(variant);
+            }
+            ::parse_variants::__private::discard_warnings(warnings_mark);
+            Err(
+                syn::Error::new(
+                    input.span(),
+                    ::alloc::__export::must_use({
+                        let res = ::alloc::fmt::format(
+                            format_args!(
+                                "parse error: tokens cannot be parsed as any variant of {0}",
+                                "EnumWithMixedVariants",
+                            ),
+                        );
+                        res
+                    }),
                 ),
-            ) as ::std::result::Result<EnumWithMixedVariants, ::syn::Error>
-        })() {
-            input.advance_to(&fork);
-            return Ok(variant);
+            )
+        })();
+        if result.is_err() {
+            ::parse_variants::__private::discard_warnings(warnings_mark);
         }
-        Err(
-            syn::Error::new(
-                input.span(),
-                ::alloc::__export::must_use({
-                    let res = ::alloc::fmt::format(
-                        format_args!(
-                            "parse error: tokens cannot be parsed as any variant of {0}",
-                            "EnumWithMixedVariants",
-                        ),
-                    );
-                    res
-                }),
-            ),
-        )
+        result
     }
 }
@@ -0,0 +1,69 @@
+enum EnumWithPriorities {
+    Expression(syn::Expr),
+    #[parse(priority = 1)]
+    Identifier(syn::Ident),
+    #[parse(priority = -1)]
+    Fallback(syn::Ident, syn::Ident),
+}
+impl ::syn::parse::Parse for EnumWithPriorities {
+    fn parse(
+        input: &::syn::parse::ParseBuffer,
+    ) -> ::std::result::Result<Self, ::syn::Error> {
+        use ::syn::parse::discouraged::Speculative;
+        let warnings_mark = ::parse_variants::__private::warnings_mark();
+        let result = (|| -> ::std::result::Result<Self, ::syn::Error> {
+            let fork = input.fork();
+            let warnings_mark = ::parse_variants::__private::warnings_mark();
+            if let Ok(variant) = (|| {
+                let field_0 = fork.parse()?;
+                Ok(EnumWithPriorities::Identifier(field_0))
+                    as ::std::result::Result<EnumWithPriorities, ::syn::Error>
+            })() {
+                input.advance_to(&fork);
+                return Ok(variant);
+            }
+            ::parse_variants::__private::discard_warnings(warnings_mark);
+            let fork = input.fork();
+            let warnings_mark = ::parse_variants::__private::warnings_mark();
+            if let Ok(variant) = (|| {
+                let field_0 = fork.parse()?;
+                Ok(EnumWithPriorities::Expression(field_0))
+                    as ::std::result::Result<EnumWithPriorities, ::syn::Error>
+            })() {
+                input.advance_to(&fork);
+                return Ok(variant);
+            }
+            ::parse_variants::__private::discard_warnings(warnings_mark);
+            let fork = input.fork();
+            let warnings_mark = ::parse_variants::__private::warnings_mark();
+            if let Ok(variant) = (|| {
+                let field_0 = fork.parse()?;
+                let field_1 = fork.parse()?;
+                Ok(EnumWithPriorities::Fallback(field_0, field_1))
+                    as ::std::result::Result<EnumWithPriorities, ::syn::Error>
+            })() {
+                input.advance_to(&fork);
+                return Ok(variant);
+            }
+            ::parse_variants::__private::discard_warnings(warnings_mark);
+            Err(
+                syn::Error::new(
+                    input.span(),
+                    ::alloc::__export::must_use({
+                        let res = ::alloc::fmt::format(
+                            format_args!(
+                                "parse error: tokens cannot be parsed as any variant of {0}",
+                                "EnumWithPriorities",
+                            ),
+                        );
+                        res
+                    }),
+                ),
+            )
+        })();
+        if result.is_err() {
+            ::parse_variants::__private::discard_warnings(warnings_mark);
+        }
+        result
+    }
+}
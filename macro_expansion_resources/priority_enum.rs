@@ -0,0 +1,8 @@
+#[derive(parse_variants::Parse)]
+enum EnumWithPriorities {
+    Expression(syn::Expr),
+    #[parse(priority = 1)]
+    Identifier(syn::Ident),
+    #[parse(priority = -1)]
+    Fallback(syn::Ident, syn::Ident),
+}
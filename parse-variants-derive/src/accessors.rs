@@ -0,0 +1,169 @@
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{DataEnum, Variant};
+
+use crate::attributes::{FieldAttributes, VariantAttributes};
+use crate::keywords::RenameRule;
+use crate::{field_locals, variant_with_locals};
+
+// generate the methods `is_<variant>`, `as_<variant>` and `into_<variant>` for each variant of the
+// enumeration, if it is marked `#[parse(accessors)]`. The methods are named after the variants in
+// snake case and have the same visibility as the enumeration. Unit variants only get `is_<variant>`.
+// Variants with more than one field give access to a tuple of their fields in order of declaration.
+// Example:
+//
+//    impl MyEnum {
+//        pub fn is_identifier(&self) -> bool {
+//            ::std::matches!(self, MyEnum::Identifier { .. })
+//        }
+//        pub fn as_identifier(&self) -> ::std::option::Option<&Ident> {
+//            match self {
+//                MyEnum::Identifier(field_0) => ::std::option::Option::Some(field_0),
+//                _ => ::std::option::Option::None,
+//            }
+//        }
+//        pub fn into_identifier(self) -> ::std::result::Result<Ident, Self> {
+//            match self {
+//                MyEnum::Identifier(field_0) => ::std::result::Result::Ok(field_0),
+//                other => ::std::result::Result::Err(other),
+//            }
+//        }
+//    }
+pub(crate) fn accessors_impl(
+    visibility: &syn::Visibility,
+    enum_ident: &syn::Ident,
+    data_enum: &DataEnum,
+) -> proc_macro2::TokenStream {
+    let mut accessors = proc_macro2::TokenStream::new();
+    for variant in data_enum.variants.iter() {
+        let variant_ident = &variant.ident;
+        let snake_name = RenameRule::Snake.apply(&variant_ident.unraw().to_string());
+        let is_variant = format_ident!("is_{}", snake_name);
+        let is_doc = format!(
+            "Whether this is [`{}::{}`].",
+            enum_ident.unraw(),
+            variant_ident
+        );
+        accessors.extend(quote! {
+            #[doc = #is_doc]
+            #visibility fn #is_variant(&self) -> bool {
+                ::std::matches!(self, #enum_ident::#variant_ident { .. })
+            }
+        });
+        if variant.fields.is_empty() {
+            continue;
+        }
+
+        let as_variant = format_ident!("as_{}", snake_name);
+        let into_variant = format_ident!("into_{}", snake_name);
+        let as_doc = format!(
+            "The fields of [`{}::{}`] or `None` for other variants.",
+            enum_ident.unraw(),
+            variant_ident
+        );
+        let into_doc = format!(
+            "Convert into the fields of [`{}::{}`] or give back `self` for other variants.",
+            enum_ident.unraw(),
+            variant_ident
+        );
+        let pattern = variant_with_locals(enum_ident, variant);
+        let locals = field_locals(&variant.fields);
+        let types: Vec<_> = variant.fields.iter().map(|field| &field.ty).collect();
+        let (fields, field_refs, field_types) = match (&locals[..], &types[..]) {
+            ([local], [field_type]) => {
+                (quote! {#local}, quote! {&#field_type}, quote! {#field_type})
+            }
+            _ => (
+                quote! {(#(#locals),*)},
+                quote! {(#(&#types),*)},
+                quote! {(#(#types),*)},
+            ),
+        };
+        // for an enumeration with a single variant, the catch-all arms are unreachable
+        accessors.extend(quote! {
+            #[doc = #as_doc]
+            #visibility fn #as_variant(&self) -> ::std::option::Option<#field_refs> {
+                #[allow(unreachable_patterns)]
+                match self {
+                    #pattern => ::std::option::Option::Some(#fields),
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = #into_doc]
+            #visibility fn #into_variant(self) -> ::std::result::Result<#field_types, Self> {
+                #[allow(unreachable_patterns)]
+                match self {
+                    #pattern => ::std::result::Result::Ok(#fields),
+                    other => ::std::result::Result::Err(other),
+                }
+            }
+        });
+    }
+
+    quote! {
+        impl #enum_ident {
+            #accessors
+        }
+    }
+}
+
+// generate an implementation of `From` for the type of the field of each variant that has exactly
+// one field, if the enumeration is marked `#[parse(from)]`. The fallback variant and the variants whose
+// field is not parsed are skipped. Example:
+//
+//    impl ::std::convert::From<Ident> for MyEnum {
+//        fn from(field_0: Ident) -> Self {
+//            MyEnum::Identifier(field_0)
+//        }
+//    }
+//
+// # Returns
+// The implementations or an error if two variants have the same field type, since the
+// implementations would conflict.
+pub(crate) fn from_impls(
+    enum_ident: &syn::Ident,
+    data_enum: &DataEnum,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let mut from_impls = proc_macro2::TokenStream::new();
+    let mut converted: Vec<(String, &Variant)> = Vec::new();
+    for variant in data_enum.variants.iter() {
+        let field = match variant.fields.iter().collect::<Vec<_>>()[..] {
+            [field] => field,
+            _ => continue,
+        };
+        // a value of such a variant is more than its field, e.g. the span of its tokens
+        let field_attributes = FieldAttributes::from_attributes(&field.attrs)?;
+        if VariantAttributes::from_attributes(&variant.attrs)?.fallback
+            || field_attributes.captures_variant()
+            || field_attributes.spelling
+            || field_attributes.separators
+            || field_attributes.delimiter
+        {
+            continue;
+        }
+        let field_type = &field.ty;
+        let type_name = quote! {#field_type}.to_string();
+        if let Some((_, other)) = converted.iter().find(|(name, _)| *name == type_name) {
+            return Err(syn::Error::new(
+                field_type.span(),
+                format!(
+                    "conflicting From implementations: variants {} and {} have the same field type",
+                    other.ident, variant.ident
+                ),
+            ));
+        }
+        converted.push((type_name, variant));
+
+        let construct_variant = variant_with_locals(enum_ident, variant);
+        from_impls.extend(quote! {
+            impl ::std::convert::From<#field_type> for #enum_ident {
+                fn from(field_0: #field_type) -> Self {
+                    #construct_variant
+                }
+            }
+        });
+    }
+    Ok(from_impls)
+}
@@ -0,0 +1,462 @@
+use proc_macro2::Span;
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+use syn::spanned::Spanned;
+use syn::{Attribute, LitInt, LitStr, Type};
+
+use crate::keywords::RenameRule;
+
+// the name of the helper attribute, i.e. `#[parse(...)]`
+const PARSE_ATTRIBUTE: &str = "parse";
+
+// the options that can be given on the enumeration itself via `#[parse(...)]`
+#[derive(Default)]
+pub(crate) struct EnumAttributes {
+    // the token that is parsed once before any of the variants, if any
+    pub prefix: Option<Type>,
+    // the token that is parsed once after the variant, if any
+    pub suffix: Option<Type>,
+    // the delimiter of the group that wraps the variants, if any
+    pub delimited: Option<GroupDelimiter>,
+    // the rule that converts the names of the keyword variants into their keywords, if any
+    pub rename_all: Option<RenameRule>,
+    // whether the keywords of all keyword variants are matched case insensitively
+    pub case_insensitive: bool,
+    // whether the fieldless kind enumeration is generated for the variants
+    pub kind: bool,
+    // whether a parse function is generated for each variant
+    pub entry_points: bool,
+    // whether the methods `is_<variant>`, `as_<variant>` and `into_<variant>` are generated
+    pub accessors: bool,
+    // whether `From` is implemented for the field types of the variants with a single field
+    pub from: bool,
+    // whether `FromStr` is implemented by parsing the string
+    pub from_str: bool,
+    // whether a constructor is generated for each variant
+    pub constructors: bool,
+    // whether serde's `Serialize` and `Deserialize` are implemented via ToTokens and Parse
+    pub serde: bool,
+}
+
+impl EnumAttributes {
+    // collect all `#[parse(...)]` options from the attributes of the enumeration.
+    // An error is returned for unknown or duplicate options.
+    pub fn from_attributes(attributes: &[Attribute]) -> Result<Self, syn::Error> {
+        let mut prefix: Option<Type> = None;
+        let mut suffix: Option<Type> = None;
+        let mut delimited: Option<GroupDelimiter> = None;
+        let mut rename_all: Option<RenameRule> = None;
+        let mut case_insensitive: Option<Span> = None;
+        let mut kind: Option<Span> = None;
+        let mut entry_points: Option<Span> = None;
+        let mut accessors: Option<Span> = None;
+        let mut from: Option<Span> = None;
+        let mut from_str: Option<Span> = None;
+        let mut constructors: Option<Span> = None;
+        let mut serde: Option<Span> = None;
+
+        for attribute in attributes
+            .iter()
+            .filter(|attr| attr.path().is_ident(PARSE_ATTRIBUTE))
+        {
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("prefix") {
+                    set_once(&meta, &mut prefix, meta.value()?.parse()?)
+                } else if meta.path.is_ident("suffix") {
+                    set_once(&meta, &mut suffix, meta.value()?.parse()?)
+                } else if meta.path.is_ident("delimited") {
+                    let delimiter = meta.value()?.parse::<syn::Ident>()?;
+                    let delimiter = if delimiter == "parens" {
+                        GroupDelimiter::Parenthesized
+                    } else if delimiter == "brackets" {
+                        GroupDelimiter::Bracketed
+                    } else if delimiter == "braces" {
+                        GroupDelimiter::Braced
+                    } else {
+                        return Err(syn::Error::new(
+                            delimiter.span(),
+                            "unknown delimiter: expected `parens`, `brackets` or `braces`",
+                        ));
+                    };
+                    set_once(&meta, &mut delimited, delimiter)
+                } else if meta.path.is_ident("rename_all") {
+                    let rule = RenameRule::from_lit_str(&meta.value()?.parse()?)?;
+                    set_once(&meta, &mut rename_all, rule)
+                } else if meta.path.is_ident("case_insensitive") {
+                    set_once(&meta, &mut case_insensitive, meta.path.span())
+                } else if meta.path.is_ident("kind") {
+                    set_once(&meta, &mut kind, meta.path.span())
+                } else if meta.path.is_ident("entry_points") {
+                    set_once(&meta, &mut entry_points, meta.path.span())
+                } else if meta.path.is_ident("accessors") {
+                    set_once(&meta, &mut accessors, meta.path.span())
+                } else if meta.path.is_ident("from") {
+                    set_once(&meta, &mut from, meta.path.span())
+                } else if meta.path.is_ident("from_str") {
+                    set_once(&meta, &mut from_str, meta.path.span())
+                } else if meta.path.is_ident("constructors") {
+                    set_once(&meta, &mut constructors, meta.path.span())
+                } else if meta.path.is_ident("serde") {
+                    set_once(&meta, &mut serde, meta.path.span())
+                } else {
+                    Err(meta.error(
+                        "unknown enum attribute: expected `prefix`, `suffix`, `delimited`, `rename_all`, `case_insensitive`, `kind`, `entry_points`, `accessors`, `from`, `from_str`, `constructors` or `serde`",
+                    ))
+                }
+            })?;
+        }
+
+        Ok(Self {
+            prefix,
+            suffix,
+            delimited,
+            rename_all,
+            case_insensitive: case_insensitive.is_some(),
+            kind: kind.is_some(),
+            entry_points: entry_points.is_some(),
+            accessors: accessors.is_some(),
+            from: from.is_some(),
+            from_str: from_str.is_some(),
+            constructors: constructors.is_some(),
+            serde: serde.is_some(),
+        })
+    }
+}
+
+// the options that can be given on an enum variant via `#[parse(...)]`
+#[derive(Default)]
+pub(crate) struct VariantAttributes {
+    // the parse priority of the variant. Variants with higher priority
+    // are tried first. Variants with equal priority are tried in order of declaration.
+    pub priority: i64,
+    // whether the variant is excluded from parsing
+    pub ignore: bool,
+    // whether the variant is the catch-all variant that is tried last and collects tokens
+    pub fallback: bool,
+    // the token at which the fallback variant stops collecting tokens, if any
+    pub terminator: Option<Type>,
+    // the token that must follow the variant (without being consumed), if any
+    pub followed_by: Option<Type>,
+    // the tokens that must not follow the variant
+    pub not_followed_by: Vec<Type>,
+    // the separator that is parsed between consecutive fields of the variant, if any
+    pub separator: Option<Type>,
+    // the syntax template of the variant, if any
+    pub syntax: Option<LitStr>,
+    // the delimiter of the group that wraps all fields of the variant, if any
+    pub delimiter: Option<GroupDelimiter>,
+    // the keyword of a keyword variant, if it is not derived from the name of the variant
+    pub rename: Option<LitStr>,
+    // whether the keyword of the variant is matched case insensitively
+    pub case_insensitive: bool,
+    // the alternative spellings of a keyword variant or a token variant
+    pub aliases: Vec<LitStr>,
+    // whether ToTokens emits the canonical spelling instead of the spelling that was parsed
+    pub canonical: bool,
+    // the note of the warning that is recorded when the variant is parsed, if any
+    pub deprecated: Option<LitStr>,
+    // whether the unit variant is parsed from empty input only
+    pub empty: bool,
+}
+
+// the delimiters that can wrap a whole variant, as given by `#[parse(parenthesized)]`,
+// `#[parse(bracketed)]` or `#[parse(braced)]`, or all variants of an enumeration, as given
+// by `#[parse(delimited = parens)]`, `#[parse(delimited = brackets)]` or `#[parse(delimited = braces)]`
+#[derive(Clone, Copy)]
+pub(crate) enum GroupDelimiter {
+    Parenthesized,
+    Bracketed,
+    Braced,
+}
+
+impl GroupDelimiter {
+    // the syn macro that parses the delimited group, e.g. `::syn::parenthesized`
+    pub fn parse_macro(self) -> proc_macro2::TokenStream {
+        match self {
+            GroupDelimiter::Parenthesized => quote! {::syn::parenthesized},
+            GroupDelimiter::Bracketed => quote! {::syn::bracketed},
+            GroupDelimiter::Braced => quote! {::syn::braced},
+        }
+    }
+
+    // the name of the proc_macro2::Delimiter variant, e.g. `Parenthesis`
+    pub fn group_delimiter(self) -> proc_macro2::TokenStream {
+        match self {
+            GroupDelimiter::Parenthesized => quote! {Parenthesis},
+            GroupDelimiter::Bracketed => quote! {Bracket},
+            GroupDelimiter::Braced => quote! {Brace},
+        }
+    }
+}
+
+impl VariantAttributes {
+    // collect all `#[parse(...)]` options from the attributes of a variant.
+    // An error is returned for unknown, duplicate or contradicting options.
+    pub fn from_attributes(attributes: &[Attribute]) -> Result<Self, syn::Error> {
+        let mut priority: Option<(i64, Span)> = None;
+        let mut ignore: Option<Span> = None;
+        let mut fallback: Option<Span> = None;
+        let mut terminator: Option<Type> = None;
+        let mut followed_by: Option<Type> = None;
+        let mut not_followed_by: Vec<Type> = Vec::new();
+        let mut separator: Option<Type> = None;
+        let mut syntax: Option<LitStr> = None;
+        let mut delimiter: Option<(GroupDelimiter, Span)> = None;
+        let mut rename: Option<LitStr> = None;
+        let mut case_insensitive: Option<Span> = None;
+        let mut aliases: Vec<LitStr> = Vec::new();
+        let mut canonical: Option<Span> = None;
+        let mut deprecated: Option<LitStr> = None;
+        let mut empty: Option<Span> = None;
+
+        for attribute in attributes
+            .iter()
+            .filter(|attr| attr.path().is_ident(PARSE_ATTRIBUTE))
+        {
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("priority") {
+                    let value = (parse_signed_integer(&meta)?, meta.path.span());
+                    set_once(&meta, &mut priority, value)
+                } else if meta.path.is_ident("ignore") {
+                    set_once(&meta, &mut ignore, meta.path.span())
+                } else if meta.path.is_ident("fallback") {
+                    set_once(&meta, &mut fallback, meta.path.span())
+                } else if meta.path.is_ident("terminator") {
+                    set_once(&meta, &mut terminator, meta.value()?.parse()?)
+                } else if meta.path.is_ident("followed_by") {
+                    set_once(&meta, &mut followed_by, meta.value()?.parse()?)
+                } else if meta.path.is_ident("not_followed_by") {
+                    not_followed_by.push(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("sep") {
+                    set_once(&meta, &mut separator, meta.value()?.parse()?)
+                } else if meta.path.is_ident("syntax") {
+                    set_once(&meta, &mut syntax, meta.value()?.parse()?)
+                } else if meta.path.is_ident("parenthesized") {
+                    set_delimiter_once(&meta, &mut delimiter, GroupDelimiter::Parenthesized)
+                } else if meta.path.is_ident("bracketed") {
+                    set_delimiter_once(&meta, &mut delimiter, GroupDelimiter::Bracketed)
+                } else if meta.path.is_ident("braced") {
+                    set_delimiter_once(&meta, &mut delimiter, GroupDelimiter::Braced)
+                } else if meta.path.is_ident("rename") {
+                    set_once(&meta, &mut rename, meta.value()?.parse()?)
+                } else if meta.path.is_ident("case_insensitive") {
+                    set_once(&meta, &mut case_insensitive, meta.path.span())
+                } else if meta.path.is_ident("alias") {
+                    aliases.push(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("canonical") {
+                    set_once(&meta, &mut canonical, meta.path.span())
+                } else if meta.path.is_ident("deprecated") {
+                    set_once(&meta, &mut deprecated, meta.value()?.parse()?)
+                } else if meta.path.is_ident("empty") {
+                    set_once(&meta, &mut empty, meta.path.span())
+                } else {
+                    Err(meta.error(
+                        "unknown variant attribute: expected `priority`, `ignore`, `fallback`, `terminator`, `followed_by`, `not_followed_by`, `sep`, `syntax`, `parenthesized`, `bracketed`, `braced`, `rename`, `case_insensitive`, `alias`, `canonical`, `deprecated` or `empty`",
+                    ))
+                }
+            })?;
+        }
+
+        if let Some(ref terminator) = terminator {
+            if fallback.is_none() {
+                return Err(syn::Error::new(
+                    terminator.span(),
+                    "illegal terminator: only a #[parse(fallback)] variant can have a terminator",
+                ));
+            }
+        }
+        if let (Some((_, span)), Some(_)) = (priority, fallback) {
+            return Err(syn::Error::new(
+                span,
+                "illegal priority: the fallback variant is always tried last",
+            ));
+        }
+        if let (Some(ref separator), Some(_)) = (&separator, fallback) {
+            return Err(syn::Error::new(
+                separator.span(),
+                "illegal separator: the fields of the fallback variant are not parsed",
+            ));
+        }
+        if let (Some(ref syntax), Some(_)) = (&syntax, fallback) {
+            return Err(syn::Error::new(
+                syntax.span(),
+                "illegal syntax template: the fields of the fallback variant are not parsed",
+            ));
+        }
+        if let (Some(ref syntax), Some(_)) = (&syntax, &separator) {
+            return Err(syn::Error::new(
+                syntax.span(),
+                "contradicting attributes: separators must be written into the syntax template instead of #[parse(sep = ...)]",
+            ));
+        }
+        if let (Some((_, span)), Some(_)) = (delimiter, fallback) {
+            return Err(syn::Error::new(
+                span,
+                "illegal delimiter: the fields of the fallback variant are not parsed",
+            ));
+        }
+        if let (Some(span), true) = (
+            empty,
+            fallback.is_some() || syntax.is_some() || delimiter.is_some() || separator.is_some(),
+        ) {
+            return Err(syn::Error::new(
+                span,
+                "contradicting attributes: a variant parsed from empty input cannot have a fallback, syntax template, delimiter or separator",
+            ));
+        }
+        if let (Some(span), Some(_)) = (fallback, ignore) {
+            return Err(syn::Error::new(
+                span,
+                "contradicting attributes: a variant cannot be both ignored and the fallback",
+            ));
+        }
+
+        Ok(Self {
+            priority: priority.map(|(priority, _)| priority).unwrap_or_default(),
+            ignore: ignore.is_some(),
+            fallback: fallback.is_some(),
+            terminator,
+            followed_by,
+            not_followed_by,
+            separator,
+            syntax,
+            delimiter: delimiter.map(|(delimiter, _)| delimiter),
+            rename,
+            case_insensitive: case_insensitive.is_some(),
+            aliases,
+            canonical: canonical.is_some(),
+            deprecated,
+            empty: empty.is_some(),
+        })
+    }
+}
+
+// the options that can be given on a field of an enum variant via `#[parse(...)]`
+#[derive(Default)]
+pub(crate) struct FieldAttributes {
+    // whether parsing commits to the variant once this field was parsed successfully,
+    // i.e. subsequent errors are returned instead of trying the next variant
+    pub commit: bool,
+    // whether the field collects the separators of the variant instead of being parsed
+    pub separators: bool,
+    // whether the field stores the delimiter token of the variant instead of being parsed
+    pub delimiter: bool,
+    // whether the field keeps the tokens of the spelling that a keyword variant was parsed from
+    pub spelling: bool,
+    // whether the field holds the joined span of the tokens that the variant was parsed from
+    pub span: bool,
+    // whether the field keeps the tokens that the variant was parsed from
+    pub tokens: bool,
+    // whether the field is default constructed by the generated constructor of the variant
+    pub default: bool,
+}
+
+impl FieldAttributes {
+    // collect all `#[parse(...)]` options from the attributes of a field.
+    // An error is returned for unknown or duplicate options.
+    pub fn from_attributes(attributes: &[Attribute]) -> Result<Self, syn::Error> {
+        let mut commit: Option<Span> = None;
+        let mut separators: Option<Span> = None;
+        let mut delimiter: Option<Span> = None;
+        let mut spelling: Option<Span> = None;
+        let mut span: Option<Span> = None;
+        let mut tokens: Option<Span> = None;
+        let mut default: Option<Span> = None;
+
+        for attribute in attributes
+            .iter()
+            .filter(|attr| attr.path().is_ident(PARSE_ATTRIBUTE))
+        {
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("commit") {
+                    set_once(&meta, &mut commit, meta.path.span())
+                } else if meta.path.is_ident("separators") {
+                    set_once(&meta, &mut separators, meta.path.span())
+                } else if meta.path.is_ident("delimiter") {
+                    set_once(&meta, &mut delimiter, meta.path.span())
+                } else if meta.path.is_ident("spelling") {
+                    set_once(&meta, &mut spelling, meta.path.span())
+                } else if meta.path.is_ident("span") {
+                    set_once(&meta, &mut span, meta.path.span())
+                } else if meta.path.is_ident("tokens") {
+                    set_once(&meta, &mut tokens, meta.path.span())
+                } else if meta.path.is_ident("default") {
+                    set_once(&meta, &mut default, meta.path.span())
+                } else {
+                    Err(meta.error(
+                        "unknown field attribute: expected `commit`, `separators`, `delimiter`, `spelling`, `span`, `tokens` or `default`",
+                    ))
+                }
+            })?;
+        }
+
+        // the fields that are not parsed cannot be commit points and can only serve one purpose
+        let unparsed = [separators, delimiter, spelling, span, tokens];
+        if let Some(span) = commit {
+            if unparsed.iter().any(Option::is_some) {
+                return Err(syn::Error::new(
+                    span,
+                    "illegal commit: the field is not parsed and cannot be a commit point",
+                ));
+            }
+        }
+        if unparsed.iter().filter(|span| span.is_some()).count() > 1 {
+            return Err(syn::Error::new(
+                unparsed.iter().flatten().last().copied().unwrap_or_else(Span::call_site),
+                "contradicting attributes: a field can only be one of the separators, delimiter, spelling, span or tokens field",
+            ));
+        }
+
+        Ok(Self {
+            commit: commit.is_some(),
+            separators: separators.is_some(),
+            delimiter: delimiter.is_some(),
+            spelling: spelling.is_some(),
+            span: span.is_some(),
+            tokens: tokens.is_some(),
+            default: default.is_some(),
+        })
+    }
+
+    // whether the field is not parsed, but filled from the tokens of the whole variant after
+    // the variant was parsed
+    pub fn captures_variant(&self) -> bool {
+        self.span || self.tokens
+    }
+}
+
+// helper function to assign an option exactly once. Returns a descriptive
+// error if the option was already given.
+fn set_once<T>(meta: &ParseNestedMeta, option: &mut Option<T>, value: T) -> Result<(), syn::Error> {
+    if option.is_some() {
+        return Err(meta.error("duplicate attribute: option may only be given once"));
+    }
+    *option = Some(value);
+    Ok(())
+}
+
+// helper function to assign the delimiter of a variant exactly once. Returns a descriptive
+// error if a delimiter was already given.
+fn set_delimiter_once(
+    meta: &ParseNestedMeta,
+    option: &mut Option<(GroupDelimiter, Span)>,
+    delimiter: GroupDelimiter,
+) -> Result<(), syn::Error> {
+    if option.is_some() {
+        return Err(meta.error(
+            "duplicate delimiter: only one of `parenthesized`, `bracketed` or `braced` may be given",
+        ));
+    }
+    *option = Some((delimiter, meta.path.span()));
+    Ok(())
+}
+
+// parse an integer value like `= 3` or `= -1` for the current option
+fn parse_signed_integer(meta: &ParseNestedMeta) -> Result<i64, syn::Error> {
+    let value = meta.value()?;
+    let negative = value.parse::<Option<syn::Token![-]>>()?.is_some();
+    let magnitude: i64 = value.parse::<LitInt>()?.base10_parse()?;
+    Ok(if negative { -magnitude } else { magnitude })
+}
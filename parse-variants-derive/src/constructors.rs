@@ -0,0 +1,145 @@
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{DataEnum, Fields, Variant};
+
+use crate::attributes::{EnumAttributes, FieldAttributes, VariantAttributes};
+use crate::field_locals;
+use crate::keywords::{keyword_of_variant, RenameRule};
+use crate::syntax::SyntaxTemplate;
+
+// generate a constructor for each variant of the enumeration, if it is marked `#[parse(constructors)]`.
+// The constructors are named after the variants in snake case and have the same visibility as the
+// enumeration. Only the semantic fields are parameters, while the token fields are default constructed,
+// so that their spans are the call site. Token fields are `Token![...]` or `syn::token::...` types, the
+// separators and delimiter fields and the fields marked `#[parse(default)]`. Span fields are the call site
+// and spelling fields get the canonical spelling of the variant. Example:
+//
+//    impl MyEnum {
+//        pub fn two_expressions_separated_by_keyword(first: syn::Expr, second: syn::Expr) -> Self {
+//            MyEnum::TwoExpressionsSeparatedByKeyword {
+//                first,
+//                _the_dude: ::std::default::Default::default(),
+//                second,
+//            }
+//        }
+//    }
+pub(crate) fn constructors_impl(
+    visibility: &syn::Visibility,
+    enum_ident: &syn::Ident,
+    enum_attributes: &EnumAttributes,
+    data_enum: &DataEnum,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let mut constructors = proc_macro2::TokenStream::new();
+    for variant in data_enum.variants.iter() {
+        let variant_ident = &variant.ident;
+        // the name of a variant like `Type` is a keyword in snake case and must be a raw identifier.
+        // The keywords `crate`, `self` and `super` cannot be raw identifiers and get a trailing underscore.
+        let constructor = RenameRule::Snake.apply(&variant_ident.unraw().to_string());
+        let constructor = match syn::parse_str::<syn::Ident>(&constructor) {
+            Ok(constructor) => constructor,
+            Err(_) if ["crate", "self", "super"].contains(&constructor.as_str()) => {
+                format_ident!("{}_", constructor)
+            }
+            Err(_) => format_ident!("r#{}", constructor),
+        };
+        let doc = format!(
+            "Construct [`{}::{}`] with default tokens.",
+            enum_ident.unraw(),
+            variant_ident
+        );
+
+        let mut parameters = Vec::new();
+        let mut values = Vec::new();
+        for (field, local) in variant.fields.iter().zip(field_locals(&variant.fields)) {
+            let attributes_of_field = FieldAttributes::from_attributes(&field.attrs)?;
+            if attributes_of_field.tokens {
+                // the kept tokens would contradict the other fields, since ToTokens emits them instead
+                return Err(syn::Error::new(
+                    field.span(),
+                    "illegal tokens field: the kept tokens cannot be constructed, so the variants of an enumeration marked #[parse(constructors)] cannot have a #[parse(tokens)] field",
+                ));
+            } else if attributes_of_field.span {
+                values.push(quote! {::parse_variants::__private::proc_macro2::Span::call_site()});
+            } else if attributes_of_field.spelling {
+                let emit_spelling = emit_canonical_spelling(variant, enum_attributes)?;
+                values.push(quote! {{
+                    let mut spelling = ::parse_variants::__private::proc_macro2::TokenStream::new();
+                    let tokens = &mut spelling;
+                    #emit_spelling
+                    spelling.into_iter().collect()
+                }});
+            } else if attributes_of_field.default
+                || attributes_of_field.separators
+                || attributes_of_field.delimiter
+                || is_token_type(&field.ty)
+            {
+                values.push(quote! {::std::default::Default::default()});
+            } else {
+                let parameter = field.ident.clone().unwrap_or(local);
+                let field_type = &field.ty;
+                parameters.push(quote! {#parameter: #field_type});
+                values.push(quote! {#parameter});
+            }
+        }
+
+        let construct_variant = match variant.fields {
+            Fields::Named(ref fields_named) => {
+                let fields = fields_named.named.iter().map(|field| &field.ident);
+                quote! {#enum_ident::#variant_ident {#(#fields : #values),*}}
+            }
+            Fields::Unnamed(_) => quote! {#enum_ident::#variant_ident (#(#values),*)},
+            Fields::Unit => quote! {#enum_ident::#variant_ident},
+        };
+        constructors.extend(quote! {
+            #[doc = #doc]
+            #visibility fn #constructor(#(#parameters),*) -> Self {
+                #construct_variant
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl #enum_ident {
+            #constructors
+        }
+    })
+}
+
+// helper function to generate the statements that emit the canonical spelling of a keyword variant or
+// a token variant to the `tokens` variable, like ToTokens does for a variant without a spelling field
+fn emit_canonical_spelling(
+    variant: &Variant,
+    enum_attributes: &EnumAttributes,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let attributes = VariantAttributes::from_attributes(&variant.attrs)?;
+    Ok(match attributes.syntax {
+        Some(ref syntax) => {
+            let parsed = vec![false; variant.fields.len()];
+            SyntaxTemplate::from_variant(syntax, variant, &parsed)?.to_tokens_statements()
+        }
+        None => {
+            let keyword = keyword_of_variant(variant, enum_attributes, &attributes)?;
+            quote! {
+                ::parse_variants::__private::emit_keyword(tokens, #keyword);
+            }
+        }
+    })
+}
+
+// helper function to detect the token types of syn, i.e. `Token![...]` or types in a `token`
+// module like `syn::token::Paren`. Custom keywords cannot be detected and must be marked
+// with `#[parse(default)]` instead.
+fn is_token_type(field_type: &syn::Type) -> bool {
+    match field_type {
+        syn::Type::Macro(ref type_macro) => type_macro.mac.path.is_ident("Token"),
+        syn::Type::Path(ref type_path) => {
+            let segments = &type_path.path.segments;
+            segments
+                .iter()
+                .take(segments.len().saturating_sub(1))
+                .any(|segment| segment.ident == "token")
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,178 @@
+use syn::ext::IdentExt;
+use syn::{LitStr, Variant};
+
+use crate::attributes::{EnumAttributes, FieldAttributes, VariantAttributes};
+
+// the rules for converting the name of a unit variant into its keyword as given by
+// `#[parse(rename_all = "...")]` on the enumeration. The names of the rules are the same as in serde.
+#[derive(Clone, Copy)]
+pub(crate) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    // parse the rule from its name, e.g. `"snake_case"`
+    pub fn from_lit_str(rule: &LitStr) -> Result<Self, syn::Error> {
+        Ok(match rule.value().as_str() {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "PascalCase" => RenameRule::Pascal,
+            "camelCase" => RenameRule::Camel,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+            _ => return Err(syn::Error::new(
+                rule.span(),
+                "unknown rename rule: expected \"lowercase\", \"UPPERCASE\", \"PascalCase\", \"camelCase\", \"snake_case\", \"SCREAMING_SNAKE_CASE\", \"kebab-case\" or \"SCREAMING-KEBAB-CASE\"",
+            )),
+        })
+    }
+
+    // apply the rule to the name of a variant, which is expected in PascalCase
+    pub fn apply(self, variant_name: &str) -> String {
+        match self {
+            RenameRule::Lower => variant_name.to_ascii_lowercase(),
+            RenameRule::Upper => variant_name.to_ascii_uppercase(),
+            RenameRule::Pascal => variant_name.to_string(),
+            RenameRule::Camel => {
+                let mut chars = variant_name.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            }
+            RenameRule::Snake => {
+                // a word starts at an uppercase letter after a lowercase letter or digit, or at the
+                // last letter of a run of uppercase letters, so that `HTTPServer` is `http_server`
+                let characters: Vec<char> = variant_name.chars().collect();
+                let mut snake = String::new();
+                for (index, character) in characters.iter().enumerate() {
+                    if index > 0 && character.is_uppercase() {
+                        let previous = characters[index - 1];
+                        let next_is_lowercase =
+                            matches!(characters.get(index + 1), Some(next) if next.is_lowercase());
+                        if previous.is_lowercase()
+                            || previous.is_ascii_digit()
+                            || (previous.is_uppercase() && next_is_lowercase)
+                        {
+                            snake.push('_');
+                        }
+                    }
+                    snake.push(character.to_ascii_lowercase());
+                }
+                snake
+            }
+            RenameRule::ScreamingSnake => {
+                RenameRule::Snake.apply(variant_name).to_ascii_uppercase()
+            }
+            RenameRule::Kebab => RenameRule::Snake.apply(variant_name).replace('_', "-"),
+            RenameRule::ScreamingKebab => RenameRule::ScreamingSnake
+                .apply(variant_name)
+                .replace('_', "-"),
+        }
+    }
+}
+
+// the keyword that a keyword variant is parsed from. This is the name given by `#[parse(rename = "...")]`
+// or else the name of the variant converted by the rename rule of the enumeration, if any.
+// Keywords consist of identifiers that may be joined by `-`, like `fast-forward`.
+// # Returns
+// The keyword or an error if the keyword does not consist of identifiers.
+pub(crate) fn keyword_of_variant(
+    variant: &Variant,
+    enum_attributes: &EnumAttributes,
+    attributes: &VariantAttributes,
+) -> Result<String, syn::Error> {
+    let (keyword, span) = match attributes.rename {
+        Some(ref rename) => (rename.value(), rename.span()),
+        None => {
+            let variant_name = variant.ident.unraw().to_string();
+            let keyword = match enum_attributes.rename_all {
+                Some(rule) => rule.apply(&variant_name),
+                None => variant_name,
+            };
+            (keyword, variant.ident.span())
+        }
+    };
+
+    check_keyword(&keyword, span)?;
+    Ok(keyword)
+}
+
+// all spellings of a keyword variant, i.e. its keyword (see above) followed by the aliases given
+// by `#[parse(alias = "...")]` in order of declaration.
+// # Returns
+// The spellings or an error if any of the spellings does not consist of identifiers.
+pub(crate) fn spellings_of_keyword_variant(
+    variant: &Variant,
+    enum_attributes: &EnumAttributes,
+    attributes: &VariantAttributes,
+) -> Result<Vec<String>, syn::Error> {
+    let mut spellings = vec![keyword_of_variant(variant, enum_attributes, attributes)?];
+    for alias in attributes.aliases.iter() {
+        check_keyword(&alias.value(), alias.span())?;
+        spellings.push(alias.value());
+    }
+    Ok(spellings)
+}
+
+// helper function to check that the keyword consists of identifiers that may be joined by `-`.
+// Keywords (like `type`) are allowed as parts of the keyword, since they are identifiers
+// in the token stream, too.
+fn check_keyword(keyword: &str, span: proc_macro2::Span) -> Result<(), syn::Error> {
+    let is_identifier =
+        |word: &str| syn::parse::Parser::parse_str(syn::Ident::parse_any, word).is_ok();
+    if !keyword.split('-').all(is_identifier) {
+        return Err(syn::Error::new(
+            span,
+            format!(
+                "illegal keyword: `{}` must consist of identifiers that may be joined by `-`",
+                keyword
+            ),
+        ));
+    }
+    Ok(())
+}
+
+// whether the variant is parsed from a keyword, which is the case for all variants that have neither
+// a syntax template nor are the fallback or empty variant and whose fields (if any) are not parsed
+pub(crate) fn is_keyword_variant(variant: &Variant, attributes: &VariantAttributes) -> bool {
+    attributes.syntax.is_none()
+        && !attributes.fallback
+        && !attributes.empty
+        && has_no_parsed_fields(variant)
+}
+
+// whether the variant is parsed from a syntax template without placeholders, so that it consists
+// of tokens only. Like keyword variants, token variants can have aliases.
+pub(crate) fn is_token_variant(variant: &Variant, attributes: &VariantAttributes) -> bool {
+    attributes.syntax.is_some() && has_no_parsed_fields(variant)
+}
+
+// helper function to check whether all fields of the variant (if any) either keep the spelling or
+// capture the whole variant. Malformed field attributes are reported when the fields are parsed.
+fn has_no_parsed_fields(variant: &Variant) -> bool {
+    variant.fields.iter().all(|field| {
+        FieldAttributes::from_attributes(&field.attrs)
+            .map(|attributes_of_field| {
+                attributes_of_field.spelling || attributes_of_field.captures_variant()
+            })
+            .unwrap_or(false)
+    })
+}
+
+// describe the enumeration for error messages by splitting its name into lowercase words,
+// e.g. `LengthUnit` is described as "length unit"
+pub(crate) fn describe_enum(enum_ident: &syn::Ident) -> String {
+    RenameRule::Snake
+        .apply(&enum_ident.unraw().to_string())
+        .replace('_', " ")
+}
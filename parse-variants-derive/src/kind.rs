@@ -0,0 +1,95 @@
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::DataEnum;
+
+// generate the fieldless enumeration that mirrors the variants of the enumeration, if it is marked
+// `#[parse(kind)]`, together with the methods that give the kind and the name of a variant. The kind
+// enumeration has the same visibility as the enumeration itself. Ignored variants have a kind, too.
+// Example:
+//
+//    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+//    pub enum MyEnumKind {
+//        Pair,
+//        Name,
+//    }
+//
+//    impl MyEnumKind {
+//        pub const ALL: &'static [MyEnumKind] = &[MyEnumKind::Pair, MyEnumKind::Name];
+//        pub fn variant_name(self) -> &'static str {
+//            match self {
+//                MyEnumKind::Pair => "Pair",
+//                MyEnumKind::Name => "Name",
+//            }
+//        }
+//    }
+//
+//    impl MyEnum {
+//        pub fn kind(&self) -> MyEnumKind {
+//            match self {
+//                MyEnum::Pair { .. } => MyEnumKind::Pair,
+//                MyEnum::Name { .. } => MyEnumKind::Name,
+//            }
+//        }
+//        pub fn variant_name(&self) -> &'static str {
+//            self.kind().variant_name()
+//        }
+//    }
+pub(crate) fn kind_impl(
+    visibility: &syn::Visibility,
+    enum_ident: &syn::Ident,
+    data_enum: &DataEnum,
+) -> proc_macro2::TokenStream {
+    let kind_ident = kind_of_enum(enum_ident);
+    let variants: Vec<_> = data_enum
+        .variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect();
+    let variant_names = variants.iter().map(|variant| variant.unraw().to_string());
+    let kind_doc = format!(
+        "The variants of [`{}`] without their fields.",
+        enum_ident.unraw()
+    );
+    let variant_docs = variants
+        .iter()
+        .map(|variant| format!("The kind of [`{}::{}`].", enum_ident.unraw(), variant));
+
+    quote! {
+        #[doc = #kind_doc]
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+        #visibility enum #kind_ident {
+            #(#[doc = #variant_docs] #variants,)*
+        }
+
+        impl #kind_ident {
+            /// All kinds in order of declaration of the variants.
+            #visibility const ALL: &'static [#kind_ident] = &[#(#kind_ident::#variants),*];
+
+            /// The name of the variant as it is declared.
+            #visibility fn variant_name(self) -> &'static str {
+                match self {
+                    #(#kind_ident::#variants => #variant_names,)*
+                }
+            }
+        }
+
+        impl #enum_ident {
+            /// The kind of the variant, i.e. the variant without its fields.
+            #visibility fn kind(&self) -> #kind_ident {
+                match self {
+                    #(#enum_ident::#variants { .. } => #kind_ident::#variants,)*
+                }
+            }
+
+            /// The name of the variant as it is declared.
+            #visibility fn variant_name(&self) -> &'static str {
+                self.kind().variant_name()
+            }
+        }
+    }
+}
+
+// the name of the kind enumeration, e.g. `MyEnumKind` for `MyEnum`
+pub(crate) fn kind_of_enum(enum_ident: &syn::Ident) -> syn::Ident {
+    format_ident!("{}Kind", enum_ident.unraw())
+}
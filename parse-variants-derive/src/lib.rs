@@ -1,8 +1,21 @@
 use proc_macro::TokenStream;
 
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::spanned::Spanned;
-use syn::{parse_macro_input, Data, DataEnum, DeriveInput, Fields};
+use syn::{parse_macro_input, Data, DataEnum, DeriveInput, Fields, Variant};
+
+use crate::attributes::EnumAttributes;
+
+mod accessors;
+mod attributes;
+mod constructors;
+mod keywords;
+mod kind;
+mod parse;
+mod respan;
+mod syntax;
+mod to_tokens;
+mod visitors;
 
 /*
  See the documentation in the parse-variants crate on how to use the macro.
@@ -27,11 +40,15 @@ use syn::{parse_macro_input, Data, DataEnum, DeriveInput, Fields};
             // 1) fork input for first variant and try if we can parse it
             let fork = input.fork();
             if let Ok(variant) = (|| {   // here we use a closure to return a result or error without returning the error directly from our parse function
-                // this is how parsing named fields looks like
+                // the fields are parsed in order of declaration into local variables
+                let field_0 = fork.parse()?;
+                let field_1 = fork.parse()?;
+                let field_2 = fork.parse()?;
+                // this is how constructing a variant with named fields looks like
                 Ok(EnumWithMixedVariants::TwoExpressionsSeparatedByKeyword {
-                    first: fork.parse()?,
-                    _the_dude: fork.parse()?,
-                    second: fork.parse()?,
+                    first: field_0,
+                    _the_dude: field_1,
+                    second: field_2,
                 }) as ::std::result::Result<EnumWithMixedVariants, ::syn::Error>
             })() {
                 // if we can parse the variant, advance the parsebuffer and return immediately
@@ -41,12 +58,12 @@ use syn::{parse_macro_input, Data, DataEnum, DeriveInput, Fields};
             // 2) fork the second variant
             let fork = input.fork();
             if let Ok(variant) = (|| {   // same trick with the closure as above to catch the error returns
-                // this is how parsing named fields looks like
-                Ok(EnumWithMixedVariants::IdentifierPlusPlus(
-                    fork.parse()?,
-                    fork.parse()?,
-                    fork.parse()?,
-                )) as ::std::result::Result<EnumWithMixedVariants, ::syn::Error>
+                let field_0 = fork.parse()?;
+                let field_1 = fork.parse()?;
+                let field_2 = fork.parse()?;
+                // this is how constructing a variant with unnamed fields looks like
+                Ok(EnumWithMixedVariants::IdentifierPlusPlus(field_0, field_1, field_2))
+                    as ::std::result::Result<EnumWithMixedVariants, ::syn::Error>
             })() {
                 input.advance_to(&fork);
                 return Ok(variant);
@@ -61,82 +78,305 @@ use syn::{parse_macro_input, Data, DataEnum, DeriveInput, Fields};
             ))
         }
     }
-*/
 
-#[proc_macro_derive(Parse)]
-pub fn derive_parse_variants(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-    let enum_ident = &input.ident;
-    let data_enum = match get_data_enum(&input) {
-        Ok(data_enum) => data_enum,
-        Err(error) => {
-            return error;
-        }
-    };
+ The variants are tried in order of declaration, unless the order is changed with the
+ `#[parse(priority = N)]` attribute. Variants with higher priority are forked and tried first,
+ variants with equal priority (the default priority is 0) keep their order of declaration. So for
+
+     #[derive(crate::Parse)]
+     enum Prioritized {
+        Expression(syn::Expr),
+        #[parse(priority = 1)]
+        Identifier(syn::Ident),
+     }
+
+ the generated code first forks the input for `Prioritized::Identifier` and only afterwards
+ for `Prioritized::Expression`.
+
+ A separator given by `#[parse(sep = Token![,])]` is parsed between the fields. If the variant
+ keeps the separators in a field marked `#[parse(separators)]`, they are pushed into this field,
+ which is e.g. `field_2` for `#[parse(sep = Token![,])] Pair(Expr, Expr, #[parse(separators)] Vec<Token![,]>)`:
 
-    // here we generate the code that tries to parse the actual variants by repeatedly forking
-    // the input parse buffer and then trying to parse the input as the contents of the respective
-    // variant.
-    let mut try_parse_variants = proc_macro2::TokenStream::new();
-
-    for variant in data_enum.variants.iter() {
-        let variant_name = &variant.ident;
-        let try_parse_variant = match variant.fields {
-            Fields::Named(ref fields_named) => {
-                let fields: Vec<_> = fields_named
-                    .named
-                    .iter()
-                    .map(|field| field.ident.as_ref().unwrap())
-                    .collect();
-                // generated code looks e.g. like this
-                // Ok(MyEnum::StructLikeVariant{field1 : fork.parse()?, field2 : fork.parse()?})
-                quote! {
-                        Ok(#enum_ident::#variant_name {#(#fields : fork.parse()?),*})
+                let mut field_2 = ::std::vec::Vec::new();
+                let field_0 = fork.parse()?;
+                field_2.push(fork.parse::<Token![,]>()?);
+                let field_1 = fork.parse()?;
+                Ok(MyEnum::Pair(field_0, field_1, field_2)) as ::std::result::Result<MyEnum, ::syn::Error>
+
+ Lookahead constraints like `#[parse(followed_by = Token![;], not_followed_by = Token![=])]`
+ are checked in the closure after the fields were parsed, without consuming the peeked tokens:
+
+            let fork = input.fork();
+            if let Ok(variant) = (|| {
+                let field_0 = fork.parse()?;
+                let lookahead = fork.lookahead1();
+                if !lookahead.peek(Token![;]) {
+                    return Err(lookahead.error());
+                }
+                if fork.peek(Token![=]) {
+                    return Err(fork.error("unexpected `=`"));
                 }
+                Ok(MyEnum::Statement(field_0)) as ::std::result::Result<MyEnum, ::syn::Error>
+            })() {
+                input.advance_to(&fork);
+                return Ok(variant);
             }
-            Fields::Unnamed(ref fields_unnamed) => {
-                let fork_parse_questionmark = quote! {fork.parse()?};
-                let repeated_input_parsing =
-                    std::iter::repeat(fork_parse_questionmark).take(fields_unnamed.unnamed.len());
-                // code looks e.g. like this
-                // Ok(MyEnum::TupleLikeVariant(fork.parse()?,fork.parse()?))
-                // where fork.parse()? is repeated for each field of the tuple like variant
-                quote! {
-                    Ok(#enum_ident::#variant_name (#(#repeated_input_parsing),*))
+
+ If a field of a variant is marked with `#[parse(commit)]`, errors after this field must be
+ returned from the parse function instead of trying the next variant. So we keep track of
+ the commit point in a flag that is set once the field was parsed:
+
+            let fork = input.fork();
+            let mut committed = false;
+            let result = (|| {
+                let field_0 = fork.parse()?;
+                committed = true;
+                let field_1 = fork.parse()?;
+                Ok(MyEnum::Let {
+                    _let: field_0,
+                    name: field_1,
+                }) as ::std::result::Result<MyEnum, ::syn::Error>
+            })();
+            match result {
+                Ok(variant) => {
+                    input.advance_to(&fork);
+                    return Ok(variant);
+                }
+                Err(error) => {
+                    if committed {
+                        return Err(error);
+                    }
                 }
             }
-            Fields::Unit => {
-                // unit like variants (i.e. variants with no fields)
-                // cannot be parsed and will return a compile error
-                return syn::Error::new(
-                    variant.ident.span(),
-                    "illegal unit variant: enumeration may not have variants without fields",
-                )
-                .to_compile_error()
-                .into();
+
+ A variant marked with `#[parse(fallback)]` is always tried last. Instead of parsing its single
+ field, it collects all token trees up to the end of the input or up to the (optional) terminator
+ given by `#[parse(fallback, terminator = Token![;])]`. For a variant `Verbatim(proc_macro2::TokenStream)`
+ the generated code looks like this:
+
+            let fork = input.fork();
+            if let Ok(variant) = (|| {
+                let mut tokens = ::std::vec::Vec::new();
+                while !(fork.is_empty() || fork.peek(Token![;])) {
+                    tokens.push(fork.step(|cursor| {
+                        cursor.token_tree().ok_or_else(|| cursor.error("unexpected end of input"))
+                    })?);
+                }
+                if tokens.is_empty() {
+                    return Err(fork.error("expected at least one token"));
+                }
+                let field_0 = ::std::iter::IntoIterator::into_iter(tokens).collect::<proc_macro2::TokenStream>();
+                Ok(MyEnum::Verbatim(field_0)) as ::std::result::Result<MyEnum, ::syn::Error>
+            })() {
+                input.advance_to(&fork);
+                return Ok(variant);
             }
-        };
 
-        try_parse_variants.extend(quote! {
+ A variant with a syntax template like `#[parse(syntax = "let $name = $value ;")]` matches
+ the literal tokens of the template with helpers from the parse-variants crate and parses the
+ placeholders into the locals. Repetitions like `$($args),*` are parsed in a loop from a fork
+ of their own and collected into a `Vec` (see the syntax module):
+
+                ::parse_variants::__private::parse_ident(&fork, "let")?;
+                let field_0 = fork.parse()?;
+                ::parse_variants::__private::parse_punct(&fork, '=', false)?;
+                let field_1 = fork.parse()?;
+                ::parse_variants::__private::parse_punct(&fork, ';', false)?;
+                Ok(MyEnum::Let { name: field_0, value: field_1 }) as ::std::result::Result<MyEnum, ::syn::Error>
+
+ A variant marked `#[parse(parenthesized)]` first parses the group from the fork and then parses
+ its fields from the content by shadowing the fork. A field marked `#[parse(delimiter)]`, here `field_0`,
+ keeps the delimiter token:
+
+                let content;
+                let field_0 = ::syn::parenthesized!(content in fork);
+                let (field_1, field_2,) = {
+                    let fork = &content;
+                    let field_1 = fork.parse()?;
+                    let field_2 = fork.parse()?;
+                    if !fork.is_empty() {
+                        return Err(fork.error("unexpected token"));
+                    }
+                    (field_1, field_2,)
+                };
+
+ The prefix of an enumeration given by `#[parse(prefix = Token![#], suffix = Token![;])]` is parsed
+ from the input once before the variants are forked. Since the code for the variants returns from the
+ parse function, it is wrapped in a closure, so that the suffix can be parsed after the variant:
+
+            input.parse::<Token![#]>()?;
+            let variant = (|| -> ::std::result::Result<Self, ::syn::Error> {
+                // the variants and the generic error as above
+            })()?;
+            input.parse::<Token![;]>()?;
+            Ok(variant)
+
+ For an enumeration marked `#[parse(delimited = parens)]`, the variants are parsed from the content
+ of the group by shadowing the input:
+
+            let content;
+            ::syn::parenthesized!(content in input);
+            let variant = {
+                let input = &content;
+                let variant = (|| -> ::std::result::Result<Self, ::syn::Error> {
+                    // the variants and the generic error as above
+                })()?;
+                if !input.is_empty() {
+                    return Err(input.error("unexpected token"));
+                }
+                variant
+            };
+            Ok(variant)
+
+ Unit variants are parsed from their keyword (subject to renaming) with a helper from the
+ parse-variants crate, e.g. for `#[parse(rename_all = "kebab-case")] FastForward`:
+
+                let spelling = &fork;
+                ::parse_variants::__private::parse_keyword(&spelling, "fast-forward", false)?;
+                Ok(MyEnum::FastForward) as ::std::result::Result<MyEnum, ::syn::Error>
+
+ If the variant has aliases, e.g. `#[parse(alias = "ff")]`, each spelling is tried on a fork of its
+ own. If none of the spellings match, the canonical spelling is parsed again to return its error:
+
+                let spelling = fork.fork();
+                if (|| -> ::std::result::Result<(), ::syn::Error> {
+                    ::parse_variants::__private::parse_keyword(&spelling, "fast-forward", false)?;
+                    Ok(())
+                })().is_ok() {
+                    fork.advance_to(&spelling);
+                } else {
+                    let spelling = fork.fork();
+                    if (|| /* the same for "ff" */)().is_ok() {
+                        fork.advance_to(&spelling);
+                    } else {
+                        let spelling = &fork;
+                        ::parse_variants::__private::parse_keyword(&spelling, "fast-forward", false)?;
+                    }
+                }
+
+ If an enumeration has keyword variants, the error at the end of the parse function suggests the
+ keyword that is closest to a misspelled keyword in the input, if any:
+
+            Err(::parse_variants::__private::suggest_keyword(input, "my enum", &[("fast-forward", false), ("rewind", false)])
+                .unwrap_or_else(|| syn::Error::new(input.span(), /* the generic error as above */)))
+
+ A variant marked `#[parse(deprecated = "...")]` records a warning with a helper from the
+ parse-variants crate when it was parsed successfully, right before the input is advanced:
+
+                ::parse_variants::__private::record_warning(input.span(), "use `metres` instead");
+                input.advance_to(&fork);
+
+ Since the fields of a variant may contain deprecated variants of other enumerations, every attempt
+ to parse a variant remembers the number of recorded warnings and discards the warnings it recorded,
+ if it fails. The parse function as a whole does the same:
+
             let fork = input.fork();
-            if let Ok(variant) = (||{#try_parse_variant as ::std::result::Result<#enum_ident,::syn::Error>})() { //TODO: document this: less verbose variant than before. So that the error type can be caught at the boundary of this closure and does not propagate outside of the parse function
+            let warnings_mark = ::parse_variants::__private::warnings_mark();
+            if let Ok(variant) = (||{ /* the body of the closure as above */ })() {
                 input.advance_to(&fork);
                 return Ok(variant);
             }
-        })
+            ::parse_variants::__private::discard_warnings(warnings_mark);
+
+ A unit variant marked `#[parse(empty)]` is parsed only if the fork is empty:
+
+                if !fork.is_empty() {
+                    return Err(fork.error("expected end of input"));
+                }
+                Ok(MyEnum::None) as ::std::result::Result<MyEnum, ::syn::Error>
+
+ If a variant has a field marked `#[parse(span)]`, the cursor of the fork is remembered before
+ the fields are parsed and the span of the tokens up to the cursor afterwards is assigned to the field:
+
+                let variant_start = fork.cursor();
+                // the fields are parsed as above
+                let field_3 = ::parse_variants::__private::span_between(variant_start, fork.cursor());
+
+ Fields marked `#[parse(tokens)]` are assigned in the same way with `tokens_between` instead of `span_between`.
+
+ Variants marked with `#[parse(ignore)]` do not appear in the generated code at all, so they
+ may contain fields that do not implement `syn::parse::Parse` or be unit variants.
+
+ For an enumeration marked `#[parse(kind)]`, the Parse derive macro additionally generates the
+ fieldless kind enumeration. See the kind module for an example.
+
+ For an enumeration marked `#[parse(accessors)]` or `#[parse(from)]`, the Parse derive macro
+ additionally generates methods to access the fields of the variants or From implementations for
+ the variants with a single field. See the accessors module for an example.
+
+ For an enumeration marked `#[parse(constructors)]`, the Parse derive macro additionally generates
+ a constructor for each variant, which default constructs the token fields. See the constructors
+ module for an example.
+
+ For an enumeration marked `#[parse(from_str)]`, std::str::FromStr is implemented with a helper
+ from the parse-variants crate, which parses the string and renders the line and column into the error:
+
+    impl ::std::str::FromStr for MyEnum {
+        type Err = ::syn::Error;
+        fn from_str(source: &str) -> ::std::result::Result<Self, ::syn::Error> {
+            ::parse_variants::__private::parse_str(source)
+        }
     }
 
-    // the implementation of the derive trait
-    let parse_impl_tokens = quote! {
-        impl ::syn::parse::Parse for #enum_ident {
-            fn parse(input : & ::syn::parse::ParseBuffer) -> ::std::result::Result<Self, ::syn::Error> {
-                // we have to use this for the advante_to method in the parsing body
-                use ::syn::parse::discouraged::Speculative;
-                // parsing the variants
-                #try_parse_variants
-                // if none of the variants can be parsed, return an error
-                Err(syn::Error::new(input.span(),::std::format!{"parse error: tokens cannot be parsed as any variant of {}", ::std::stringify!{#enum_ident}}))
+ For an enumeration marked `#[parse(serde)]`, serde::Deserialize is implemented in the same way
+ after deserializing a string and serde::Serialize collects the tokens of ToTokens into a string.
+ The serde crate is reached via `::parse_variants::__private::serde`, which requires the serde feature.
+
+ For an enumeration marked `#[parse(entry_points)]`, the body of the closure of each variant is
+ reused in a function that parses only that variant. See the parse module for an example.
+
+ The ToTokens derive macro generates an implementation of quote::ToTokens, which emits
+ the fields of each variant in order of declaration. See the to_tokens module for an example.
+
+ The Respan derive macro generates an implementation of parse_variants::Respan, which respans
+ the fields of each variant. See the respan module for an example.
+
+ The visitors attribute macro generates the traits Visit, VisitMut and Fold for the enumerations
+ and structs of an inline module, in the style of syn::visit. See the visitors module for an example.
+*/
+
+#[proc_macro_derive(Parse, attributes(parse))]
+pub fn derive_parse_variants(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let data_enum = match get_data_enum(&input) {
+        Ok(data_enum) => data_enum,
+        Err(error) => {
+            return error;
+        }
+    };
+
+    // the kind enumeration, the accessors, the From implementations and the constructors are generated
+    // alongside the Parse implementation, if requested
+    let parse_impl_tokens = parse::parse_impl(&input.vis, &input.ident, &input.attrs, data_enum)
+        .and_then(|mut parse_impl_tokens| {
+            let enum_attributes = EnumAttributes::from_attributes(&input.attrs)?;
+            if enum_attributes.kind {
+                parse_impl_tokens.extend(kind::kind_impl(&input.vis, &input.ident, data_enum));
+            }
+            if enum_attributes.accessors {
+                parse_impl_tokens.extend(accessors::accessors_impl(
+                    &input.vis,
+                    &input.ident,
+                    data_enum,
+                ));
             }
+            if enum_attributes.from {
+                parse_impl_tokens.extend(accessors::from_impls(&input.ident, data_enum)?);
+            }
+            if enum_attributes.constructors {
+                parse_impl_tokens.extend(constructors::constructors_impl(
+                    &input.vis,
+                    &input.ident,
+                    &enum_attributes,
+                    data_enum,
+                )?);
+            }
+            Ok(parse_impl_tokens)
+        });
+    let parse_impl_tokens = match parse_impl_tokens {
+        Ok(parse_impl_tokens) => parse_impl_tokens,
+        Err(error) => {
+            return error.to_compile_error().into();
         }
     };
 
@@ -146,6 +386,85 @@ pub fn derive_parse_variants(input: TokenStream) -> TokenStream {
     parse_impl_tokens.into()
 }
 
+#[proc_macro_derive(ToTokens, attributes(parse))]
+pub fn derive_to_tokens(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let data_enum = match get_data_enum(&input) {
+        Ok(data_enum) => data_enum,
+        Err(error) => {
+            return error;
+        }
+    };
+
+    match to_tokens::to_tokens_impl(&input.ident, &input.attrs, data_enum) {
+        Ok(to_tokens_impl_tokens) => to_tokens_impl_tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(Respan, attributes(parse))]
+pub fn derive_respan(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let data_enum = match get_data_enum(&input) {
+        Ok(data_enum) => data_enum,
+        Err(error) => {
+            return error;
+        }
+    };
+
+    match respan::respan_impl(&input.ident, data_enum) {
+        Ok(respan_impl_tokens) => respan_impl_tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_attribute]
+pub fn visitors(args: TokenStream, input: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        return syn::Error::new(
+            proc_macro2::TokenStream::from(args).span(),
+            "unexpected arguments: the attribute does not take arguments",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let item_mod = parse_macro_input!(input as syn::ItemMod);
+    match visitors::visitors_impl(item_mod) {
+        Ok(visitors_tokens) => visitors_tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+// helper function to generate the names of the local variables that hold the values of the given
+// fields in the generated code. The locals are called `field_0`, `field_1`, ... in order of
+// declaration of the fields. We don't use the names of the fields themselves, so that the locals
+// cannot clash with the other variables in the generated code.
+fn field_locals(fields: &Fields) -> Vec<syn::Ident> {
+    (0..fields.len())
+        .map(|index| format_ident!("field_{}", index))
+        .collect()
+}
+
+// helper function to generate `MyEnum::Variant{first : field_0, second : field_1}` for struct
+// like variants or `MyEnum::Variant(field_0, field_1)` for tuple like variants. This can be used
+// both as an expression to construct the variant from the locals and as a pattern to destructure
+// the variant into the locals.
+fn variant_with_locals(enum_ident: &syn::Ident, variant: &Variant) -> proc_macro2::TokenStream {
+    let variant_name = &variant.ident;
+    let locals = field_locals(&variant.fields);
+    match variant.fields {
+        Fields::Named(ref fields_named) => {
+            let fields = fields_named
+                .named
+                .iter()
+                .map(|field| field.ident.as_ref().unwrap());
+            quote! {#enum_ident::#variant_name {#(#fields : #locals),*}}
+        }
+        Fields::Unnamed(_) => quote! {#enum_ident::#variant_name (#(#locals),*)},
+        Fields::Unit => quote! {#enum_ident::#variant_name},
+    }
+}
+
 // helper function to return the DataEnum of the derive input.
 // # Returns
 // the DataEnum field, if the derive input is an enum and if the enum has at least one variant.
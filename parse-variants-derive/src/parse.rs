@@ -0,0 +1,978 @@
+use quote::{format_ident, quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{DataEnum, Variant};
+
+use crate::attributes::{EnumAttributes, FieldAttributes, GroupDelimiter, VariantAttributes};
+use crate::keywords::{
+    describe_enum, is_keyword_variant, is_token_variant, spellings_of_keyword_variant, RenameRule,
+};
+use crate::kind::kind_of_enum;
+use crate::syntax::SyntaxTemplate;
+use crate::{field_locals, variant_with_locals};
+
+// generate the implementation of the syn::parse::Parse trait for the enum. See the top of the
+// crate for an example of how the generated code looks.
+pub(crate) fn parse_impl(
+    visibility: &syn::Visibility,
+    enum_ident: &syn::Ident,
+    enum_attributes: &[syn::Attribute],
+    data_enum: &DataEnum,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let enum_attributes = EnumAttributes::from_attributes(enum_attributes)?;
+    let variants = variants_in_parse_order(enum_ident, data_enum)?;
+
+    // here we generate the code that tries to parse the actual variants by repeatedly forking
+    // the input parse buffer and then trying to parse the input as the contents of the respective
+    // variant.
+    let mut try_parse_variants = proc_macro2::TokenStream::new();
+    let mut keywords = Vec::new();
+    let mut entry_points = Vec::new();
+    for (variant, attributes) in variants {
+        if enum_attributes.entry_points {
+            entry_points.push(entry_point(
+                visibility,
+                enum_ident,
+                &enum_attributes,
+                variant,
+                &attributes,
+            )?);
+        }
+        if is_keyword_variant(variant, &attributes) {
+            let case_insensitive = enum_attributes.case_insensitive || attributes.case_insensitive;
+            keywords.extend(
+                spellings_of_keyword_variant(variant, &enum_attributes, &attributes)?
+                    .into_iter()
+                    .map(|spelling| quote! {(#spelling, #case_insensitive)}),
+            );
+        }
+        try_parse_variants.extend(try_parse_variant(
+            enum_ident,
+            &enum_attributes,
+            variant,
+            &attributes,
+        )?);
+    }
+    let generic_error = quote! {
+        syn::Error::new(input.span(),::std::format!{"parse error: tokens cannot be parsed as any variant of {}", ::std::stringify!{#enum_ident}})
+    };
+    // if the enumeration has keyword variants and the input is a misspelled keyword, the
+    // error suggests the closest keyword instead of giving the generic error
+    let error = if keywords.is_empty() {
+        generic_error
+    } else {
+        let description = describe_enum(enum_ident);
+        quote! {
+            ::parse_variants::__private::suggest_keyword(input, #description, &[#(#keywords),*])
+                .unwrap_or_else(|| #generic_error)
+        }
+    };
+    let try_parse_variants = quote! {
+        // parsing the variants
+        #try_parse_variants
+        // if none of the variants can be parsed, return an error
+        Err(#error)
+    };
+
+    let parse_body = discard_warnings_on_error(wrap_variants(&enum_attributes, try_parse_variants));
+    let entry_points = match enum_attributes.entry_points {
+        true => Some(entry_points_impl(
+            visibility,
+            enum_ident,
+            &enum_attributes,
+            data_enum,
+            entry_points,
+        )?),
+        false => None,
+    };
+    // the string is parsed with a helper that renders the location of the error into its message
+    let from_str = enum_attributes.from_str.then(|| {
+        quote! {
+            impl ::std::str::FromStr for #enum_ident {
+                type Err = ::syn::Error;
+                fn from_str(source: &str) -> ::std::result::Result<Self, ::syn::Error> {
+                    ::parse_variants::__private::parse_str(source)
+                }
+            }
+        }
+    });
+
+    // the string is deserialized and then parsed like for FromStr, while the tokens of the
+    // enumeration are serialized as a string. This requires the serde feature of parse-variants.
+    let serde = enum_attributes.serde.then(|| {
+        quote! {
+            impl<'de> ::parse_variants::__private::serde::Deserialize<'de> for #enum_ident {
+                fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+                where
+                    D: ::parse_variants::__private::serde::Deserializer<'de>,
+                {
+                    let source = <::std::string::String as ::parse_variants::__private::serde::Deserialize>::deserialize(deserializer)?;
+                    ::parse_variants::__private::parse_str(&source)
+                        .map_err(<D::Error as ::parse_variants::__private::serde::de::Error>::custom)
+                }
+            }
+
+            impl ::parse_variants::__private::serde::Serialize for #enum_ident {
+                fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+                where
+                    S: ::parse_variants::__private::serde::Serializer,
+                {
+                    serializer.collect_str(&::parse_variants::__private::quote::ToTokens::to_token_stream(self))
+                }
+            }
+        }
+    });
+
+    // the implementation of the derive trait
+    Ok(quote! {
+        impl ::syn::parse::Parse for #enum_ident {
+            fn parse(input : & ::syn::parse::ParseBuffer) -> ::std::result::Result<Self, ::syn::Error> {
+                // we have to use this for the advante_to method in the parsing body
+                use ::syn::parse::discouraged::Speculative;
+                #parse_body
+            }
+        }
+
+        #entry_points
+        #from_str
+        #serde
+    })
+}
+
+// helper function to wrap the code that parses the variants, such that the prefix of the enumeration
+// is parsed before and the suffix after the variant. For a delimited enumeration, the variants are
+// parsed from the content of the group. The given code must return the variant from the parse function.
+fn wrap_variants(
+    enum_attributes: &EnumAttributes,
+    try_parse_variants: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    // the prefix is parsed only once before the variants are forked
+    let parse_prefix = enum_attributes.prefix.as_ref().map(|prefix| {
+        quote! {
+            input.parse::<#prefix>()?;
+        }
+    });
+
+    // the variants return from the parse function directly, so if anything has to be parsed after
+    // the variant, we catch the variant in a closure first
+    match (&enum_attributes.suffix, enum_attributes.delimited) {
+        (None, None) => quote! {
+            #parse_prefix
+            #try_parse_variants
+        },
+        (suffix, delimited) => {
+            let parse_suffix = suffix.as_ref().map(|suffix| {
+                quote! {
+                    input.parse::<#suffix>()?;
+                }
+            });
+            let catch_variant = quote! {
+                let variant = (|| -> ::std::result::Result<Self, ::syn::Error> {
+                    #try_parse_variants
+                })()?;
+            };
+            // the variants of a delimited enumeration are parsed from the content of the
+            // group by shadowing the input. The content must be used up.
+            let parse_variant = match delimited {
+                Some(delimited) => {
+                    let parse_macro = delimited.parse_macro();
+                    quote! {
+                        let content;
+                        #parse_macro!(content in input);
+                        let variant = {
+                            let input = &content;
+                            #catch_variant
+                            if !input.is_empty() {
+                                return Err(input.error("unexpected token"));
+                            }
+                            variant
+                        };
+                    }
+                }
+                None => catch_variant,
+            };
+            quote! {
+                #parse_prefix
+                #parse_variant
+                #parse_suffix
+                Ok(variant)
+            }
+        }
+    }
+}
+
+// helper function to return the variants of the enum in the order in which they are tried
+// during parsing. Variants are sorted by descending priority as given by the `#[parse(priority = N)]`
+// attribute. The sort is stable, so variants of equal priority keep their order of declaration.
+// Variants marked with `#[parse(ignore)]` are not part of the returned list.
+// If the attributes of a variant are malformed or if all variants are ignored, an error is returned.
+fn variants_in_parse_order<'a>(
+    enum_ident: &syn::Ident,
+    data_enum: &'a DataEnum,
+) -> Result<Vec<(&'a Variant, VariantAttributes)>, syn::Error> {
+    let mut prioritized_variants = Vec::new();
+    let mut has_fallback = false;
+    for variant in data_enum.variants.iter() {
+        let attributes = VariantAttributes::from_attributes(&variant.attrs)?;
+        if attributes.fallback {
+            if has_fallback {
+                return Err(syn::Error::new(
+                    variant.ident.span(),
+                    "duplicate fallback: at most one variant may be marked #[parse(fallback)]",
+                ));
+            }
+            has_fallback = true;
+        }
+        if !attributes.ignore {
+            prioritized_variants.push((variant, attributes));
+        }
+    }
+
+    if prioritized_variants.is_empty() {
+        return Err(syn::Error::new(
+            enum_ident.span(),
+            "no parseable variants: enumeration must have at least one variant that is not ignored",
+        ));
+    }
+
+    // the fallback variant always comes last, regardless of the priorities
+    prioritized_variants.sort_by_key(|(_, attributes)| {
+        (attributes.fallback, std::cmp::Reverse(attributes.priority))
+    });
+    Ok(prioritized_variants)
+}
+
+// helper function to generate the code that forks the input and tries to parse the given variant
+// from the fork. If the variant can be parsed, the input is advanced to the fork and the variant
+// is returned from the parse function. Otherwise, the next variant is tried, unless the variant
+// failed after a commit point. A warning is recorded for a deprecated variant that was parsed, while
+// the warnings that were recorded by a failed attempt are discarded.
+fn try_parse_variant(
+    enum_ident: &syn::Ident,
+    enum_attributes: &EnumAttributes,
+    variant: &Variant,
+    attributes: &VariantAttributes,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let (try_parse_variant, commits) =
+        parse_variant_body(enum_ident, enum_attributes, variant, attributes)?;
+    let record_warning = record_warning(attributes);
+
+    if commits {
+        // if the variant contains a field marked with #[parse(commit)], we must know whether
+        // the error occurred after the commit point. In that case, the error is returned
+        // immediately and no other variants are tried.
+        Ok(quote! {
+            let fork = input.fork();
+            let mut committed = false;
+            let warnings_mark = ::parse_variants::__private::warnings_mark();
+            let result = (||{#try_parse_variant})();
+            match result {
+                Ok(variant) => {
+                    #record_warning
+                    input.advance_to(&fork);
+                    return Ok(variant);
+                }
+                Err(error) => {
+                    ::parse_variants::__private::discard_warnings(warnings_mark);
+                    if committed {
+                        return Err(error);
+                    }
+                }
+            }
+        })
+    } else {
+        Ok(quote! {
+            let fork = input.fork();
+            let warnings_mark = ::parse_variants::__private::warnings_mark();
+            if let Ok(variant) = (||{#try_parse_variant})() {
+                #record_warning
+                input.advance_to(&fork);
+                return Ok(variant);
+            }
+            ::parse_variants::__private::discard_warnings(warnings_mark);
+        })
+    }
+}
+
+// helper function to generate the body of the closure that parses the given variant from the fork.
+// The body parses the fields into local variables, checks the lookahead constraints and then
+// constructs the variant. We use a closure, so that the error can be caught at the boundary of
+// this closure and does not propagate outside of the parse function.
+// # Returns
+// The body and a flag indicating whether any field commits to the variant.
+// If the attributes of the variant or its fields are malformed, an error is returned.
+fn parse_variant_body(
+    enum_ident: &syn::Ident,
+    enum_attributes: &EnumAttributes,
+    variant: &Variant,
+    attributes: &VariantAttributes,
+) -> Result<(proc_macro2::TokenStream, bool), syn::Error> {
+    let is_keyword_variant = is_keyword_variant(variant, attributes);
+    let is_token_variant = is_token_variant(variant, attributes);
+    if (attributes.rename.is_some() || attributes.case_insensitive) && !is_keyword_variant {
+        return Err(syn::Error::new(
+            variant.ident.span(),
+            "illegal keyword attributes: only unit variants without a syntax template are parsed from keywords",
+        ));
+    }
+    if (!attributes.aliases.is_empty() || attributes.canonical)
+        && !(is_keyword_variant || is_token_variant)
+    {
+        return Err(syn::Error::new(
+            variant.ident.span(),
+            "illegal spelling attributes: only keyword variants and syntax templates without placeholders have spellings",
+        ));
+    }
+
+    let (parse_fields, commits) = if attributes.empty {
+        (parse_empty(variant)?, false)
+    } else if is_keyword_variant || is_token_variant {
+        (
+            parse_spellings(variant, enum_attributes, attributes)?,
+            false,
+        )
+    } else if attributes.fallback {
+        (collect_fallback_tokens(variant, attributes)?, false)
+    } else if let Some(ref syntax) = attributes.syntax {
+        parse_syntax_template(variant, attributes, syntax)?
+    } else {
+        parse_fields(variant, attributes)?
+    };
+    let parse_fields = match attributes.delimiter {
+        Some(delimiter) => parse_delimited(variant, delimiter, parse_fields)?,
+        None => parse_fields,
+    };
+    let parse_fields = capture_variant(variant, parse_fields)?;
+    let lookahead_checks = lookahead_checks(attributes);
+    let construct_variant = variant_with_locals(enum_ident, variant);
+
+    Ok((
+        quote! {
+            #parse_fields
+            #lookahead_checks
+            Ok(#construct_variant) as ::std::result::Result<#enum_ident,::syn::Error>
+        },
+        commits,
+    ))
+}
+
+// helper function to wrap the body of a parse function, so that the warnings that were recorded
+// while parsing are discarded if the parse function fails, e.g. because the suffix is missing
+fn discard_warnings_on_error(parse_body: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    quote! {
+        let warnings_mark = ::parse_variants::__private::warnings_mark();
+        let result = (|| -> ::std::result::Result<Self, ::syn::Error> {
+            #parse_body
+        })();
+        if result.is_err() {
+            ::parse_variants::__private::discard_warnings(warnings_mark);
+        }
+        result
+    }
+}
+
+// helper function to generate the statement that records the warning for a deprecated variant
+// once it was parsed successfully, if the variant is deprecated
+fn record_warning(attributes: &VariantAttributes) -> Option<proc_macro2::TokenStream> {
+    attributes.deprecated.as_ref().map(|deprecated| {
+        quote! {
+            ::parse_variants::__private::record_warning(input.span(), #deprecated);
+        }
+    })
+}
+
+// helper function to generate the function that parses only the given variant, if the enumeration
+// is marked `#[parse(entry_points)]`. The function is called after the variant, e.g. `parse_fast_forward`
+// for `FastForward`, and parses the prefix, suffix or delimiters of the enumeration like the parse
+// function. Since no other variants are tried, the error of the variant itself is returned.
+// The function has the same visibility as the enumeration. Example:
+//
+//    pub fn parse_fast_forward(input: ::syn::parse::ParseStream) -> ::std::result::Result<Self, ::syn::Error> {
+//        use ::syn::parse::discouraged::Speculative;
+//        let fork = input.fork();
+//        let variant = (||{ /* the body of the closure as above */ })()?;
+//        input.advance_to(&fork);
+//        Ok(variant)
+//    }
+fn entry_point(
+    visibility: &syn::Visibility,
+    enum_ident: &syn::Ident,
+    enum_attributes: &EnumAttributes,
+    variant: &Variant,
+    attributes: &VariantAttributes,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let (try_parse_variant, commits) =
+        parse_variant_body(enum_ident, enum_attributes, variant, attributes)?;
+    let record_warning = record_warning(attributes);
+    // the commit points make no difference here, but the flag must exist for the body
+    let (declare_committed, read_committed) = match commits {
+        true => (
+            Some(quote! {let mut committed = false;}),
+            Some(quote! {let _ = committed;}),
+        ),
+        false => (None, None),
+    };
+    let parse_body = discard_warnings_on_error(wrap_variants(
+        enum_attributes,
+        quote! {
+            let fork = input.fork();
+            #declare_committed
+            let variant = (||{#try_parse_variant})()?;
+            #read_committed
+            #record_warning
+            input.advance_to(&fork);
+            return Ok(variant);
+        },
+    ));
+    let entry_point = entry_point_of_variant(variant);
+    let doc = format!(
+        "Parse the input as [`{}::{}`] only.",
+        enum_ident.unraw(),
+        variant.ident
+    );
+
+    Ok(quote! {
+        #[doc = #doc]
+        #visibility fn #entry_point(input: ::syn::parse::ParseStream) -> ::std::result::Result<Self, ::syn::Error> {
+            use ::syn::parse::discouraged::Speculative;
+            #parse_body
+        }
+    })
+}
+
+// helper function to generate the implementation block with the entry points of all variants that
+// are not ignored. If the enumeration is also marked `#[parse(kind)]`, the block contains the function
+// `parse_variant`, which parses the variant of the given kind. Example:
+//
+//    impl MyEnum {
+//        pub fn parse_fast_forward(input: ::syn::parse::ParseStream) -> ::std::result::Result<Self, ::syn::Error> {
+//            // see above
+//        }
+//        pub fn parse_variant(kind: MyEnumKind, input: ::syn::parse::ParseStream) -> ::std::result::Result<Self, ::syn::Error> {
+//            match kind {
+//                MyEnumKind::FastForward => Self::parse_fast_forward(input),
+//                MyEnumKind::Ignored => Err(input.error("parse error: variant Ignored of MyEnum is ignored")),
+//            }
+//        }
+//    }
+fn entry_points_impl(
+    visibility: &syn::Visibility,
+    enum_ident: &syn::Ident,
+    enum_attributes: &EnumAttributes,
+    data_enum: &DataEnum,
+    entry_points: Vec<proc_macro2::TokenStream>,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    // the entry points of two variants like `HTTPServer` and `HttpServer` can have the same name,
+    // and the entry point of a variant `Variant` clashes with `parse_variant`
+    let mut names: Vec<(String, &Variant)> = Vec::new();
+    for variant in data_enum.variants.iter() {
+        if VariantAttributes::from_attributes(&variant.attrs)?.ignore {
+            continue;
+        }
+        let name = entry_point_of_variant(variant).to_string();
+        if enum_attributes.kind && name == "parse_variant" {
+            return Err(syn::Error::new(
+                variant.ident.span(),
+                "conflicting entry points: the entry point of this variant clashes with `parse_variant`, which is generated for `#[parse(kind)]`",
+            ));
+        }
+        if let Some((_, other)) = names.iter().find(|(other_name, _)| *other_name == name) {
+            return Err(syn::Error::new(
+                variant.ident.span(),
+                format!(
+                    "conflicting entry points: variants {} and {} both have the entry point `{}`",
+                    other.ident, variant.ident, name
+                ),
+            ));
+        }
+        names.push((name, variant));
+    }
+
+    let parse_variant = match enum_attributes.kind {
+        true => {
+            let kind_ident = kind_of_enum(enum_ident);
+            let mut match_arms = proc_macro2::TokenStream::new();
+            for variant in data_enum.variants.iter() {
+                let variant_ident = &variant.ident;
+                match_arms.extend(
+                    if VariantAttributes::from_attributes(&variant.attrs)?.ignore {
+                        let message = format!(
+                            "parse error: variant {} of {} is ignored",
+                            variant_ident.unraw(),
+                            enum_ident.unraw()
+                        );
+                        quote! {
+                            #kind_ident::#variant_ident => Err(input.error(#message)),
+                        }
+                    } else {
+                        let entry_point = entry_point_of_variant(variant);
+                        quote! {
+                            #kind_ident::#variant_ident => Self::#entry_point(input),
+                        }
+                    },
+                );
+            }
+            Some(quote! {
+                /// Parse the input as the variant of the given kind only.
+                #visibility fn parse_variant(kind: #kind_ident, input: ::syn::parse::ParseStream) -> ::std::result::Result<Self, ::syn::Error> {
+                    match kind {
+                        #match_arms
+                    }
+                }
+            })
+        }
+        false => None,
+    };
+
+    Ok(quote! {
+        impl #enum_ident {
+            #(#entry_points)*
+            #parse_variant
+        }
+    })
+}
+
+// the name of the entry point of the variant, e.g. `parse_fast_forward` for `FastForward`
+fn entry_point_of_variant(variant: &Variant) -> syn::Ident {
+    format_ident!(
+        "parse_{}",
+        RenameRule::Snake.apply(&variant.ident.unraw().to_string())
+    )
+}
+
+// helper function to generate the statements that parse the fields of the variant from the fork
+// into the local variables `field_0`, `field_1`, ... in order of declaration.
+// * A field marked with `#[parse(commit)]` additionally sets the `committed` flag after it was parsed.
+// * If the variant has a separator given by `#[parse(sep = ...)]`, the separator is parsed between
+//   consecutive fields. The separators are collected into the field marked `#[parse(separators)]`, if any.
+// # Returns
+// The statements and a flag indicating whether any field commits to the variant.
+// If the attributes of a field are malformed, an error is returned.
+fn parse_fields(
+    variant: &Variant,
+    attributes: &VariantAttributes,
+) -> Result<(proc_macro2::TokenStream, bool), syn::Error> {
+    let locals = field_locals(&variant.fields);
+    let mut field_attributes = Vec::new();
+    let mut separators_local = None;
+    let mut has_delimiter_field = false;
+    for (field, local) in variant.fields.iter().zip(locals.iter()) {
+        let attributes_of_field = FieldAttributes::from_attributes(&field.attrs)?;
+        check_delimiter_field(
+            field,
+            &attributes_of_field,
+            attributes,
+            &mut has_delimiter_field,
+        )?;
+        check_spelling_field(field, &attributes_of_field)?;
+        if attributes_of_field.separators {
+            if attributes.separator.is_none() {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "illegal separators field: the variant must have a separator given by #[parse(sep = ...)]",
+                ));
+            }
+            if separators_local.is_some() {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "duplicate separators field: at most one field may be marked #[parse(separators)]",
+                ));
+            }
+            separators_local = Some(local);
+        }
+        field_attributes.push(attributes_of_field);
+    }
+
+    let mut statements = proc_macro2::TokenStream::new();
+    if let Some(separators_local) = separators_local {
+        statements.extend(quote! {
+            let mut #separators_local = ::std::vec::Vec::new();
+        });
+    }
+
+    let mut commits = false;
+    let parsed_fields = locals
+        .iter()
+        .zip(field_attributes)
+        .filter(|(_, attributes_of_field)| {
+            !attributes_of_field.separators
+                && !attributes_of_field.delimiter
+                && !attributes_of_field.captures_variant()
+        });
+    for (index, (local, attributes_of_field)) in parsed_fields.enumerate() {
+        if let (Some(ref separator), true) = (&attributes.separator, index > 0) {
+            statements.extend(match separators_local {
+                Some(separators_local) => quote! {
+                    #separators_local.push(fork.parse::<#separator>()?);
+                },
+                None => quote! {
+                    fork.parse::<#separator>()?;
+                },
+            });
+        }
+
+        statements.extend(quote! {
+            let #local = fork.parse()?;
+        });
+        if attributes_of_field.commit {
+            commits = true;
+            statements.extend(quote! {
+                committed = true;
+            });
+        }
+    }
+    Ok((statements, commits))
+}
+
+// helper function to generate the statements that parse a keyword variant or a token variant,
+// i.e. a variant with a syntax template without placeholders. The keyword of a keyword variant
+// is the name of the variant subject to `#[parse(rename = "...")]` or `#[parse(rename_all = "...")]`.
+// It is matched case insensitively if either the variant or the enumeration is marked
+// `#[parse(case_insensitive)]`. A variant can have alternative spellings given by
+// `#[parse(alias = "...")]`. The tokens of the spelling that was parsed are kept in the field marked
+// `#[parse(spelling)]`, if any.
+fn parse_spellings(
+    variant: &Variant,
+    enum_attributes: &EnumAttributes,
+    attributes: &VariantAttributes,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let mut spelling_local = None;
+    for (field, local) in variant.fields.iter().zip(field_locals(&variant.fields)) {
+        if !FieldAttributes::from_attributes(&field.attrs)?.spelling {
+            continue;
+        }
+        if spelling_local.is_some() {
+            return Err(syn::Error::new(
+                field.span(),
+                "duplicate spelling field: at most one field may be marked #[parse(spelling)]",
+            ));
+        }
+        spelling_local = Some(local);
+    }
+
+    // the statements that parse each of the spellings from the parse stream `spelling`, where
+    // the canonical spelling comes first. Keywords with more words are tried first, so that
+    // a spelling cannot match only the first words of another spelling, like `fast` in `fast-forward`.
+    let spelling = format_ident!("spelling");
+    let mut parse_spellings = Vec::new();
+    match attributes.syntax {
+        Some(ref syntax) => {
+            let parsed = vec![false; variant.fields.len()];
+            for template in std::iter::once(syntax).chain(attributes.aliases.iter()) {
+                let template = SyntaxTemplate::from_variant(template, variant, &parsed)?;
+                parse_spellings.push((0, template.parse_statements(&spelling, &[])));
+            }
+        }
+        None => {
+            let case_insensitive = enum_attributes.case_insensitive || attributes.case_insensitive;
+            for keyword in spellings_of_keyword_variant(variant, enum_attributes, attributes)? {
+                parse_spellings.push((
+                    keyword.split('-').count(),
+                    quote! {
+                        ::parse_variants::__private::parse_keyword(&spelling, #keyword, #case_insensitive)?;
+                    },
+                ));
+            }
+        }
+    }
+    let parse_canonical_spelling = parse_spellings[0].1.clone();
+    parse_spellings.sort_by_key(|(words, _)| std::cmp::Reverse(*words));
+
+    // without aliases, the canonical spelling is parsed directly from the fork. Otherwise, all
+    // spellings are tried one after the other on a fork of their own. If none of them match, the
+    // canonical spelling is parsed again to return its error.
+    let mut parse_any_spelling = quote! {
+        let spelling = &fork;
+        #parse_canonical_spelling
+    };
+    if parse_spellings.len() > 1 {
+        for (_, parse_spelling) in parse_spellings.into_iter().rev() {
+            parse_any_spelling = quote! {
+                let spelling = fork.fork();
+                if (|| -> ::std::result::Result<(), ::syn::Error> {
+                    #parse_spelling
+                    Ok(())
+                })()
+                .is_ok()
+                {
+                    fork.advance_to(&spelling);
+                } else {
+                    #parse_any_spelling
+                }
+            };
+        }
+    }
+
+    Ok(match spelling_local {
+        Some(spelling_local) => quote! {
+            let spelling_start = fork.cursor();
+            #parse_any_spelling
+            let #spelling_local = ::parse_variants::__private::tokens_between(spelling_start, fork.cursor());
+        },
+        None => parse_any_spelling,
+    })
+}
+
+// helper function to generate the statement that checks that the input is empty for the unit
+// variant marked `#[parse(empty)]`. Like `syn::parse::Nothing`, nothing is consumed, but unlike it,
+// the variant is only parsed if there are no tokens left.
+// # Returns
+// The statement or an error if the variant is not a unit variant.
+fn parse_empty(variant: &Variant) -> Result<proc_macro2::TokenStream, syn::Error> {
+    if !variant.fields.is_empty() {
+        return Err(syn::Error::new(
+            variant.ident.span(),
+            "illegal empty variant: only unit variants can be parsed from empty input",
+        ));
+    }
+    Ok(quote! {
+        if !fork.is_empty() {
+            return Err(fork.error("expected end of input"));
+        }
+    })
+}
+
+// helper function to generate the statements that parse the variant according to its syntax
+// template given by `#[parse(syntax = "...")]`. The literal tokens of the template are matched
+// and dropped, while the placeholders are parsed into the locals `field_0`, `field_1`, ....
+// Unlike for other variants, this also works for unit variants.
+// # Returns
+// The statements and a flag indicating whether any field commits to the variant.
+// If the template is malformed or does not match the fields of the variant, an error is returned.
+fn parse_syntax_template(
+    variant: &Variant,
+    attributes: &VariantAttributes,
+    syntax: &syn::LitStr,
+) -> Result<(proc_macro2::TokenStream, bool), syn::Error> {
+    let mut commits = Vec::new();
+    let mut parsed = Vec::new();
+    let mut has_delimiter_field = false;
+    for field in variant.fields.iter() {
+        let attributes_of_field = FieldAttributes::from_attributes(&field.attrs)?;
+        if attributes_of_field.separators {
+            return Err(syn::Error::new(
+                field.span(),
+                "illegal separators field: separators of a syntax template are not kept",
+            ));
+        }
+        check_delimiter_field(
+            field,
+            &attributes_of_field,
+            attributes,
+            &mut has_delimiter_field,
+        )?;
+        check_spelling_field(field, &attributes_of_field)?;
+        commits.push(attributes_of_field.commit);
+        parsed.push(!attributes_of_field.delimiter && !attributes_of_field.captures_variant());
+    }
+    let template = SyntaxTemplate::from_variant(syntax, variant, &parsed)?;
+    let statements = template.parse_statements(&format_ident!("fork"), &commits);
+    Ok((statements, commits.contains(&true)))
+}
+
+// helper function to wrap the statements that parse the fields of a variant, such that the fields
+// are parsed from the content of the delimited group given by `#[parse(parenthesized)]`,
+// `#[parse(bracketed)]` or `#[parse(braced)]`. The content must be used up completely. The delimiter
+// token is stored in the field marked `#[parse(delimiter)]`, if any. Since the group is parsed
+// from the fork of the variant, the wrapper is as fork safe as the statements it wraps.
+fn parse_delimited(
+    variant: &Variant,
+    delimiter: GroupDelimiter,
+    parse_fields: proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let mut inner_locals = Vec::new();
+    let mut delimiter_local = None;
+    for (field, local) in variant.fields.iter().zip(field_locals(&variant.fields)) {
+        let attributes_of_field = FieldAttributes::from_attributes(&field.attrs)?;
+        if attributes_of_field.delimiter {
+            delimiter_local = Some(local);
+        } else if !attributes_of_field.captures_variant() {
+            inner_locals.push(local);
+        }
+    }
+    let store_delimiter = delimiter_local.map(|local| quote! {let #local = });
+    let parse_macro = delimiter.parse_macro();
+
+    // the fields are parsed from the content by shadowing the fork
+    Ok(quote! {
+        let content;
+        #store_delimiter #parse_macro!(content in fork);
+        let (#(#inner_locals,)*) = {
+            let fork = &content;
+            #parse_fields
+            if !fork.is_empty() {
+                return Err(fork.error("unexpected token"));
+            }
+            (#(#inner_locals,)*)
+        };
+    })
+}
+
+// helper function to wrap the statements that parse the fields of a variant, such that the fields
+// marked `#[parse(span)]` are assigned the joined span of the tokens that the variant was parsed from
+// and the fields marked `#[parse(tokens)]` are assigned the tokens themselves. The field type must be
+// able to collect token trees. If the variant has no such fields, the statements are returned as they are.
+fn capture_variant(
+    variant: &Variant,
+    parse_fields: proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let mut capture_fields = proc_macro2::TokenStream::new();
+    for (field, local) in variant.fields.iter().zip(field_locals(&variant.fields)) {
+        let attributes_of_field = FieldAttributes::from_attributes(&field.attrs)?;
+        if attributes_of_field.span {
+            capture_fields.extend(quote! {
+                let #local = ::parse_variants::__private::span_between(variant_start, fork.cursor());
+            });
+        }
+        if attributes_of_field.tokens {
+            capture_fields.extend(quote! {
+                let #local = ::parse_variants::__private::tokens_between(variant_start, fork.cursor());
+            });
+        }
+    }
+    if capture_fields.is_empty() {
+        return Ok(parse_fields);
+    }
+    Ok(quote! {
+        let variant_start = fork.cursor();
+        #parse_fields
+        #capture_fields
+    })
+}
+
+// helper function to check that a field marked with `#[parse(delimiter)]` belongs to a variant
+// with a delimiter and that it is the only such field of the variant. The flag `has_delimiter_field`
+// keeps track of whether a delimiter field was already seen.
+fn check_delimiter_field(
+    field: &syn::Field,
+    attributes_of_field: &FieldAttributes,
+    attributes: &VariantAttributes,
+    has_delimiter_field: &mut bool,
+) -> Result<(), syn::Error> {
+    if !attributes_of_field.delimiter {
+        return Ok(());
+    }
+    if attributes.delimiter.is_none() {
+        return Err(syn::Error::new(
+            field.span(),
+            "illegal delimiter field: the variant must be marked #[parse(parenthesized)], #[parse(bracketed)] or #[parse(braced)]",
+        ));
+    }
+    if *has_delimiter_field {
+        return Err(syn::Error::new(
+            field.span(),
+            "duplicate delimiter field: at most one field may be marked #[parse(delimiter)]",
+        ));
+    }
+    *has_delimiter_field = true;
+    Ok(())
+}
+
+// helper function to give an error for a field marked with `#[parse(spelling)]` in a variant that
+// is neither a keyword variant nor a token variant
+fn check_spelling_field(
+    field: &syn::Field,
+    attributes_of_field: &FieldAttributes,
+) -> Result<(), syn::Error> {
+    if attributes_of_field.spelling {
+        return Err(syn::Error::new(
+            field.span(),
+            "illegal spelling field: only keyword variants and syntax templates without placeholders have spellings",
+        ));
+    }
+    Ok(())
+}
+
+// helper function to generate the statements that collect the tokens for the fallback variant,
+// i.e. the variant marked with `#[parse(fallback)]`. The fallback variant must have exactly one
+// field, which collects all token trees up to the given terminator (exclusively) or up to the
+// end of the input. At least one token tree must be collected for the fallback variant to succeed.
+// # Returns
+// The statements that assign the collected tokens to the local `field_0` or an error if the
+// variant does not have exactly one field.
+fn collect_fallback_tokens(
+    variant: &Variant,
+    attributes: &VariantAttributes,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let field = match variant.fields.iter().collect::<Vec<_>>()[..] {
+        [field] => field,
+        _ => {
+            return Err(syn::Error::new(
+                variant.ident.span(),
+                "illegal fallback variant: the fallback variant must have exactly one field",
+            ))
+        }
+    };
+
+    let field_attributes = FieldAttributes::from_attributes(&field.attrs)?;
+    if field_attributes.commit
+        || field_attributes.separators
+        || field_attributes.delimiter
+        || field_attributes.spelling
+        || field_attributes.captures_variant()
+    {
+        return Err(syn::Error::new(
+            field.span(),
+            "illegal field attribute: the field of the fallback variant is not parsed",
+        ));
+    }
+
+    // the field type must be able to collect token trees. We span the collection
+    // to the field type, so that the compile error points to the offending type.
+    let field_type = &field.ty;
+    let collect_tokens = quote_spanned! {field_type.span()=>
+        ::std::iter::IntoIterator::into_iter(tokens).collect::<#field_type>()
+    };
+    let at_end = match attributes.terminator {
+        Some(ref terminator) => quote! {fork.is_empty() || fork.peek(#terminator)},
+        None => quote! {fork.is_empty()},
+    };
+
+    Ok(quote! {
+        let mut tokens = ::std::vec::Vec::new();
+        while !(#at_end) {
+            tokens.push(fork.step(|cursor| {
+                cursor.token_tree().ok_or_else(|| cursor.error("unexpected end of input"))
+            })?);
+        }
+        if tokens.is_empty() {
+            return Err(fork.error("expected at least one token"));
+        }
+        let field_0 = #collect_tokens;
+    })
+}
+
+// helper function to generate the checks for the lookahead constraints of a variant given by
+// `#[parse(followed_by = ...)]` or `#[parse(not_followed_by = ...)]`. The tokens after the
+// variant are peeked, but not consumed.
+fn lookahead_checks(attributes: &VariantAttributes) -> proc_macro2::TokenStream {
+    let mut lookahead_checks = proc_macro2::TokenStream::new();
+    if let Some(ref followed_by) = attributes.followed_by {
+        lookahead_checks.extend(quote! {
+            let lookahead = fork.lookahead1();
+            if !lookahead.peek(#followed_by) {
+                return Err(lookahead.error());
+            }
+        });
+    }
+    for not_followed_by in attributes.not_followed_by.iter() {
+        let message = format!("unexpected {}", describe_token(not_followed_by));
+        lookahead_checks.extend(quote! {
+            if fork.peek(#not_followed_by) {
+                return Err(fork.error(#message));
+            }
+        });
+    }
+    lookahead_checks
+}
+
+// helper function to give a human readable description of a token type for error messages.
+// For `Token![=]` this is "`=`", other types are described by their path.
+fn describe_token(token: &syn::Type) -> String {
+    match token {
+        syn::Type::Macro(ref type_macro) if type_macro.mac.path.is_ident("Token") => {
+            format!("`{}`", type_macro.mac.tokens)
+        }
+        _ => format!("`{}`", quote! {#token}.to_string().replace(' ', "")),
+    }
+}
@@ -0,0 +1,54 @@
+use quote::quote;
+use syn::{DataEnum, Fields};
+
+use crate::attributes::VariantAttributes;
+use crate::variant_with_locals;
+
+// generate the implementation of the parse_variants::Respan trait for the enum. The implementation
+// respans all fields of the variant, so the types of the fields must implement Respan, too. Ignored
+// variants are left as they are, because their fields cannot be expected to implement Respan. Example:
+//
+//    impl ::parse_variants::Respan for MyEnum {
+//        fn respan(&mut self, span: ::parse_variants::__private::proc_macro2::Span) {
+//            match self {
+//                MyEnum::Pair { first: field_0, second: field_1 } => {
+//                    ::parse_variants::Respan::respan(field_0, span);
+//                    ::parse_variants::Respan::respan(field_1, span);
+//                }
+//                MyEnum::Ignored { .. } => {}
+//            }
+//        }
+//    }
+pub(crate) fn respan_impl(
+    enum_ident: &syn::Ident,
+    data_enum: &DataEnum,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let mut match_arms = proc_macro2::TokenStream::new();
+    for variant in data_enum.variants.iter() {
+        let variant_name = &variant.ident;
+        if VariantAttributes::from_attributes(&variant.attrs)?.ignore {
+            match_arms.extend(quote! {#enum_ident::#variant_name {..} => {}});
+            continue;
+        }
+        let pattern = match variant.fields {
+            Fields::Unit => quote! {#enum_ident::#variant_name},
+            _ => variant_with_locals(enum_ident, variant),
+        };
+        let locals = crate::field_locals(&variant.fields);
+        match_arms.extend(quote! {
+            #pattern => {
+                #(::parse_variants::Respan::respan(#locals, span);)*
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl ::parse_variants::Respan for #enum_ident {
+            fn respan(&mut self, span: ::parse_variants::__private::proc_macro2::Span) {
+                match self {
+                    #match_arms
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,609 @@
+use proc_macro2::{Delimiter, Punct, Spacing, TokenStream, TokenTree};
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{Fields, LitStr, Variant};
+
+// a syntax template as given by `#[parse(syntax = "let $name = $value ;")]` on a variant.
+// The template consists of literal tokens, which are matched and dropped during parsing, and of
+// placeholders, which are parsed into the fields of the variant. Placeholders refer to named fields
+// by name (`$name`) and to unnamed fields by index (`$0`). Like in `macro_rules!`, placeholders can be
+// repeated with `$(...)*`, `$(...)+` or `$(...)?`, optionally with a separator for the first two,
+// e.g. `$($args),*`. A literal `$` is written as `$$`.
+pub(crate) struct SyntaxTemplate {
+    items: Vec<SyntaxItem>,
+}
+
+enum SyntaxItem {
+    // a literal identifier, punctuation character or literal
+    Token(TokenTree),
+    // a placeholder for the field with the given index
+    Placeholder(usize),
+    // a delimited group with its own template inside
+    Group(Delimiter, Vec<SyntaxItem>),
+    Repetition(Repetition),
+}
+
+struct Repetition {
+    items: Vec<SyntaxItem>,
+    separator: Option<TokenTree>,
+    kind: RepetitionKind,
+    // the indices of the fields that have a placeholder inside the repetition
+    fields: Vec<usize>,
+}
+
+#[derive(PartialEq, Eq)]
+enum RepetitionKind {
+    ZeroOrMore,
+    OneOrMore,
+    Optional,
+}
+
+impl SyntaxTemplate {
+    // parse the syntax template for the given variant. The parsed fields of the variant are given
+    // by `parsed`, which has an entry for every field. Every parsed field must appear exactly
+    // once in the template and every placeholder must refer to a parsed field.
+    pub fn from_variant(
+        template: &LitStr,
+        variant: &Variant,
+        parsed: &[bool],
+    ) -> Result<Self, syn::Error> {
+        let tokens: TokenStream = template.value().parse().map_err(|_| {
+            syn::Error::new(
+                template.span(),
+                "illegal syntax template: template is not a valid token stream",
+            )
+        })?;
+        let mut parser = TemplateParser {
+            template,
+            variant,
+            occurrences: vec![0; variant.fields.len()],
+            in_repetition: false,
+        };
+        let items = parser.parse_items(tokens)?;
+
+        for (index, (field, occurrences)) in
+            variant.fields.iter().zip(parser.occurrences).enumerate()
+        {
+            if parsed[index] && occurrences != 1 {
+                let field_name = match field.ident {
+                    Some(ref ident) => format!("${}", ident),
+                    None => format!("${}", index),
+                };
+                return Err(syn::Error::new(
+                    field.span(),
+                    format!(
+                        "illegal syntax template: placeholder `{}` must appear exactly once in the template",
+                        field_name
+                    ),
+                ));
+            }
+            if !parsed[index] && occurrences != 0 {
+                return Err(syn::Error::new(
+                    template.span(),
+                    "illegal syntax template: placeholders can only refer to fields that are parsed",
+                ));
+            }
+        }
+        Ok(Self { items })
+    }
+
+    // generate the statements that parse the template from the given parse stream into the
+    // local variables `field_0`, `field_1`, .... The flags `commits` indicate for every field,
+    // whether parsing commits to the variant after the field was parsed.
+    pub fn parse_statements(&self, stream: &syn::Ident, commits: &[bool]) -> TokenStream {
+        let mut counter = 0;
+        parse_items(&self.items, stream, commits, false, &mut counter)
+    }
+
+    // generate the statements that emit the template to the `tokens` variable for ToTokens.
+    // The fields are expected in the (borrowed) local variables `field_0`, `field_1`, ....
+    pub fn to_tokens_statements(&self) -> TokenStream {
+        emit_items(&self.items, false)
+    }
+}
+
+// helper for parsing a syntax template
+struct TemplateParser<'a> {
+    template: &'a LitStr,
+    variant: &'a Variant,
+    // how often each field has been referenced by a placeholder
+    occurrences: Vec<usize>,
+    in_repetition: bool,
+}
+
+impl TemplateParser<'_> {
+    fn error(&self, message: &str) -> syn::Error {
+        syn::Error::new(
+            self.template.span(),
+            format!("illegal syntax template: {}", message),
+        )
+    }
+
+    fn parse_items(&mut self, tokens: TokenStream) -> Result<Vec<SyntaxItem>, syn::Error> {
+        let mut items = Vec::new();
+        let mut tokens = tokens.into_iter();
+        while let Some(token) = tokens.next() {
+            match token {
+                TokenTree::Punct(ref punct) if punct.as_char() == '$' => match tokens.next() {
+                    Some(TokenTree::Punct(ref dollar)) if dollar.as_char() == '$' => {
+                        items.push(SyntaxItem::Token(TokenTree::Punct(dollar.clone())));
+                    }
+                    Some(TokenTree::Ident(ident)) => {
+                        let index = self.field_index(|field_name, _| {
+                            field_name.map(|name| name == &ident).unwrap_or(false)
+                        })?;
+                        items.push(SyntaxItem::Placeholder(index));
+                    }
+                    Some(TokenTree::Literal(literal)) => {
+                        let literal = literal.to_string();
+                        let index = self.field_index(|field_name, index| {
+                            field_name.is_none() && literal == index.to_string()
+                        })?;
+                        items.push(SyntaxItem::Placeholder(index));
+                    }
+                    Some(TokenTree::Group(group))
+                        if group.delimiter() == Delimiter::Parenthesis =>
+                    {
+                        items.push(SyntaxItem::Repetition(
+                            self.parse_repetition(group.stream(), &mut tokens)?,
+                        ));
+                    }
+                    _ => {
+                        return Err(self.error(
+                            "expected a field name, a field index, `$(...)` or `$$` after `$`",
+                        ))
+                    }
+                },
+                TokenTree::Group(group) => {
+                    items.push(SyntaxItem::Group(
+                        group.delimiter(),
+                        self.parse_items(group.stream())?,
+                    ));
+                }
+                token => items.push(SyntaxItem::Token(token)),
+            }
+        }
+
+        // a punctuation character is only joint with the next item, if that item is a literal
+        // punctuation character as well. E.g. the `;` in `$a;$b` is lexed as joint with the `$`.
+        for index in 0..items.len() {
+            let next_is_punct = matches!(
+                items.get(index + 1),
+                Some(SyntaxItem::Token(TokenTree::Punct(_)))
+            );
+            if let SyntaxItem::Token(TokenTree::Punct(ref mut punct)) = items[index] {
+                if !next_is_punct {
+                    *punct = alone(punct);
+                }
+            }
+        }
+        Ok(items)
+    }
+
+    // parse the repetition `$(...)` after the parenthesized group, i.e. the optional separator and
+    // the repetition operator
+    fn parse_repetition(
+        &mut self,
+        group: TokenStream,
+        tokens: &mut impl Iterator<Item = TokenTree>,
+    ) -> Result<Repetition, syn::Error> {
+        if self.in_repetition {
+            return Err(self.error("repetitions cannot be nested"));
+        }
+        let occurrences_before = self.occurrences.clone();
+        self.in_repetition = true;
+        let items = self.parse_items(group)?;
+        self.in_repetition = false;
+        let fields = self
+            .occurrences
+            .iter()
+            .zip(occurrences_before)
+            .enumerate()
+            .filter(|(_, (after, before))| **after > *before)
+            .map(|(index, _)| index)
+            .collect();
+
+        let operator = |token: &Option<TokenTree>| match token {
+            Some(TokenTree::Punct(ref punct)) => match punct.as_char() {
+                '*' => Some(RepetitionKind::ZeroOrMore),
+                '+' => Some(RepetitionKind::OneOrMore),
+                '?' => Some(RepetitionKind::Optional),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let token = tokens.next();
+        let (separator, kind) = match operator(&token) {
+            Some(kind) => (None, kind),
+            None => {
+                let next = tokens.next();
+                match operator(&next) {
+                    Some(RepetitionKind::Optional) => {
+                        return Err(self.error("the `?` repetition cannot have a separator"))
+                    }
+                    Some(kind) => {
+                        // the separator is lexed as joint with the repetition operator
+                        let separator = match token {
+                            Some(TokenTree::Punct(ref punct)) => {
+                                Some(TokenTree::Punct(alone(punct)))
+                            }
+                            token => token,
+                        };
+                        (separator, kind)
+                    }
+                    // a separator like `=>` or `::` consists of several punctuation characters
+                    None if matches!(
+                        (&token, &next),
+                        (Some(TokenTree::Punct(_)), Some(TokenTree::Punct(_)))
+                    ) =>
+                    {
+                        return Err(self.error(
+                            "the separator of a repetition `$(...)` must be a single token like `,` or `;`",
+                        ))
+                    }
+                    None => {
+                        return Err(
+                            self.error("expected `*`, `+` or `?` after a repetition `$(...)`")
+                        )
+                    }
+                }
+            }
+        };
+
+        Ok(Repetition {
+            items,
+            separator,
+            kind,
+            fields,
+        })
+    }
+
+    // find the index of the field that satisfies the given predicate, which is given the
+    // name of the field (if any) and its index
+    fn field_index(
+        &mut self,
+        predicate: impl Fn(Option<&syn::Ident>, usize) -> bool,
+    ) -> Result<usize, syn::Error> {
+        let position = self
+            .variant
+            .fields
+            .iter()
+            .enumerate()
+            .position(|(index, field)| predicate(field.ident.as_ref(), index));
+        match position {
+            Some(index) => {
+                self.occurrences[index] += 1;
+                Ok(index)
+            }
+            None => {
+                let fields_by = match self.variant.fields {
+                    Fields::Named(_) => "placeholders must be field names of the variant",
+                    _ => "placeholders must be field indices of the variant",
+                };
+                Err(self.error(&format!("unknown placeholder: {}", fields_by)))
+            }
+        }
+    }
+}
+
+// helper to give a copy of the punctuation character with `Spacing::Alone`
+fn alone(punct: &Punct) -> Punct {
+    let mut alone = Punct::new(punct.as_char(), Spacing::Alone);
+    alone.set_span(punct.span());
+    alone
+}
+
+// the name of the local variable that holds the value of the field with the given index.
+// Inside a repetition, the single values are first parsed into `value_0`, `value_1`, ...
+// and then collected into `field_0`, `field_1`, ....
+fn local(index: usize, in_repetition: bool) -> syn::Ident {
+    if in_repetition {
+        format_ident!("value_{}", index)
+    } else {
+        format_ident!("field_{}", index)
+    }
+}
+
+// generate the code that matches a literal token of the template and drops it
+fn parse_token(token: &TokenTree, stream: &syn::Ident) -> TokenStream {
+    match token {
+        TokenTree::Ident(ident) => {
+            let ident = ident.to_string();
+            quote! {::parse_variants::__private::parse_ident(&#stream, #ident)?;}
+        }
+        TokenTree::Punct(punct) => {
+            let character = punct.as_char();
+            let joint = punct.spacing() == Spacing::Joint;
+            quote! {::parse_variants::__private::parse_punct(&#stream, #character, #joint)?;}
+        }
+        TokenTree::Literal(literal) => {
+            let literal = literal.to_string();
+            quote! {::parse_variants::__private::parse_literal(&#stream, #literal)?;}
+        }
+        TokenTree::Group(_) => unreachable!("groups are not literal tokens of the template"),
+    }
+}
+
+fn parse_items(
+    items: &[SyntaxItem],
+    stream: &syn::Ident,
+    commits: &[bool],
+    in_repetition: bool,
+    counter: &mut usize,
+) -> TokenStream {
+    let mut statements = TokenStream::new();
+    for item in items {
+        statements.extend(match item {
+            SyntaxItem::Token(token) => parse_token(token, stream),
+            SyntaxItem::Placeholder(index) => {
+                let local = local(*index, in_repetition);
+                // inside a repetition, the commit only counts once the repetition is accepted
+                let commit = match (commits[*index], in_repetition) {
+                    (true, true) => quote! {repetition_committed = true;},
+                    (true, false) => quote! {committed = true;},
+                    (false, _) => TokenStream::new(),
+                };
+                quote! {
+                    let #local = #stream.parse()?;
+                    #commit
+                }
+            }
+            SyntaxItem::Group(delimiter, items) => {
+                *counter += 1;
+                let content = format_ident!("content_{}", *counter);
+                let delimited = match delimiter {
+                    Delimiter::Parenthesis => quote! {::syn::parenthesized!},
+                    Delimiter::Bracket => quote! {::syn::bracketed!},
+                    Delimiter::Brace => quote! {::syn::braced!},
+                    Delimiter::None => unreachable!("templates are parsed from strings"),
+                };
+                let parse_content = parse_items(items, &content, commits, in_repetition, counter);
+                quote! {
+                    let #content;
+                    #delimited(#content in #stream);
+                    #parse_content
+                    if !#content.is_empty() {
+                        return Err(#content.error("unexpected token"));
+                    }
+                }
+            }
+            SyntaxItem::Repetition(repetition) => {
+                parse_repetition(repetition, stream, commits, counter)
+            }
+        });
+    }
+    statements
+}
+
+fn parse_repetition(
+    repetition: &Repetition,
+    stream: &syn::Ident,
+    commits: &[bool],
+    counter: &mut usize,
+) -> TokenStream {
+    *counter += 1;
+    let fork = format_ident!("repetition_{}", *counter);
+    let count = format_ident!("repetitions_{}", *counter);
+    let parse_items = parse_items(&repetition.items, &fork, commits, true, counter);
+    let fields: Vec<_> = repetition
+        .fields
+        .iter()
+        .map(|index| local(*index, false))
+        .collect();
+    let values: Vec<_> = repetition
+        .fields
+        .iter()
+        .map(|index| local(*index, true))
+        .collect();
+
+    // a commit point inside the repetition is recorded in a local, which commits the variant only
+    // if the repetition is accepted. Otherwise, a failed repetition would commit the variant.
+    let (declare_commit, accept_commit) = if repetition.fields.iter().any(|index| commits[*index]) {
+        (
+            quote! {let mut repetition_committed = false;},
+            quote! {committed |= repetition_committed;},
+        )
+    } else {
+        (TokenStream::new(), TokenStream::new())
+    };
+
+    // the repetition is parsed from a fork, so that a failed repetition does not consume tokens.
+    // The warnings that were recorded by a failed repetition are discarded.
+    let try_parse_repetition = quote! {
+        let #fork = #stream.fork();
+        #declare_commit
+        let warnings_mark = ::parse_variants::__private::warnings_mark();
+        let result = (|| -> ::std::result::Result<_, ::syn::Error> {
+            #parse_items
+            Ok((#(#values,)*))
+        })();
+    };
+
+    if repetition.kind == RepetitionKind::Optional {
+        return quote! {
+            #(let mut #fields = ::std::option::Option::None;)*
+            #try_parse_repetition
+            if let Ok((#(#values,)*)) = result {
+                #stream.advance_to(&#fork);
+                #accept_commit
+                #(#fields = ::std::option::Option::Some(#values);)*
+            } else {
+                ::parse_variants::__private::discard_warnings(warnings_mark);
+            }
+        };
+    }
+
+    let parse_separator = match repetition.separator {
+        Some(ref separator) => {
+            let parse_separator = parse_token(separator, &fork);
+            quote! {
+                if #count > 0 {
+                    #parse_separator
+                }
+            }
+        }
+        None => TokenStream::new(),
+    };
+    let check_at_least_one = if repetition.kind == RepetitionKind::OneOrMore {
+        quote! {
+            if #count == 0 {
+                return Err(#stream.error("expected at least one repetition"));
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    // the number of repetitions is only kept track of, if it is needed
+    let (declare_count, increment_count) =
+        if repetition.separator.is_some() || repetition.kind == RepetitionKind::OneOrMore {
+            (quote! {let mut #count: usize = 0;}, quote! {#count += 1;})
+        } else {
+            (TokenStream::new(), TokenStream::new())
+        };
+
+    quote! {
+        #(let mut #fields = ::std::vec::Vec::new();)*
+        #declare_count
+        loop {
+            let #fork = #stream.fork();
+            #declare_commit
+            let warnings_mark = ::parse_variants::__private::warnings_mark();
+            let result = (|| -> ::std::result::Result<_, ::syn::Error> {
+                #parse_separator
+                #parse_items
+                Ok((#(#values,)*))
+            })();
+            match result {
+                // we stop if the repetition does not consume any tokens to avoid an infinite loop
+                Ok((#(#values,)*)) if #fork.cursor() != #stream.cursor() => {
+                    #stream.advance_to(&#fork);
+                    #accept_commit
+                    #(#fields.push(#values);)*
+                    #increment_count
+                }
+                _ => {
+                    ::parse_variants::__private::discard_warnings(warnings_mark);
+                    break;
+                }
+            }
+        }
+        #check_at_least_one
+    }
+}
+
+// generate the code that emits a literal token of the template
+fn emit_token(token: &TokenTree) -> TokenStream {
+    match token {
+        TokenTree::Ident(ident) => {
+            let ident = ident.to_string();
+            quote! {::parse_variants::__private::emit_ident(tokens, #ident);}
+        }
+        TokenTree::Punct(punct) => {
+            let character = punct.as_char();
+            let joint = punct.spacing() == Spacing::Joint;
+            quote! {::parse_variants::__private::emit_punct(tokens, #character, #joint);}
+        }
+        TokenTree::Literal(literal) => {
+            let literal = literal.to_string();
+            quote! {::parse_variants::__private::emit_literal(tokens, #literal);}
+        }
+        TokenTree::Group(_) => unreachable!("groups are not literal tokens of the template"),
+    }
+}
+
+fn emit_items(items: &[SyntaxItem], in_repetition: bool) -> TokenStream {
+    let mut statements = TokenStream::new();
+    for item in items {
+        statements.extend(match item {
+            SyntaxItem::Token(token) => emit_token(token),
+            SyntaxItem::Placeholder(index) => {
+                let local = local(*index, in_repetition);
+                quote! {::parse_variants::__private::quote::ToTokens::to_tokens(#local, tokens);}
+            }
+            SyntaxItem::Group(delimiter, items) => {
+                let delimiter = match delimiter {
+                    Delimiter::Parenthesis => quote! {Parenthesis},
+                    Delimiter::Bracket => quote! {Bracket},
+                    Delimiter::Brace => quote! {Brace},
+                    Delimiter::None => unreachable!("templates are parsed from strings"),
+                };
+                let emit_content = emit_items(items, in_repetition);
+                quote! {
+                    ::parse_variants::__private::emit_group(
+                        tokens,
+                        ::parse_variants::__private::proc_macro2::Delimiter::#delimiter,
+                        |tokens| {
+                            #emit_content
+                        },
+                    );
+                }
+            }
+            SyntaxItem::Repetition(repetition) => emit_repetition(repetition),
+        });
+    }
+    statements
+}
+
+fn emit_repetition(repetition: &Repetition) -> TokenStream {
+    let emit_items = emit_items(&repetition.items, true);
+    let fields: Vec<_> = repetition
+        .fields
+        .iter()
+        .map(|index| local(*index, false))
+        .collect();
+    let values: Vec<_> = repetition
+        .fields
+        .iter()
+        .map(|index| local(*index, true))
+        .collect();
+
+    // a repetition without placeholders does not know how often it was repeated, so we
+    // emit it as often as required
+    if fields.is_empty() {
+        return if repetition.kind == RepetitionKind::OneOrMore {
+            emit_items
+        } else {
+            TokenStream::new()
+        };
+    }
+
+    if repetition.kind == RepetitionKind::Optional {
+        return quote! {
+            if let (#(::std::option::Option::Some(#values),)*) = (#(#fields,)*) {
+                #emit_items
+            }
+        };
+    }
+
+    // zip the values of all fields in the repetition, i.e. for three fields we iterate
+    // over `((value_0, value_1), value_2)`
+    let first_field = &fields[0];
+    let first_value = &values[0];
+    let mut zipped_fields = quote! {::std::iter::IntoIterator::into_iter(#first_field)};
+    let mut zipped_values = quote! {#first_value};
+    for (field, value) in fields.iter().zip(values.iter()).skip(1) {
+        zipped_fields = quote! {::std::iter::Iterator::zip(#zipped_fields, #field)};
+        zipped_values = quote! {(#zipped_values, #value)};
+    }
+
+    match repetition.separator {
+        Some(ref separator) => {
+            let emit_separator = emit_token(separator);
+            quote! {
+                for (index, #zipped_values) in ::std::iter::Iterator::enumerate(#zipped_fields) {
+                    if index > 0 {
+                        #emit_separator
+                    }
+                    #emit_items
+                }
+            }
+        }
+        None => quote! {
+            for #zipped_values in #zipped_fields {
+                #emit_items
+            }
+        },
+    }
+}
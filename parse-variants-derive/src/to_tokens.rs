@@ -0,0 +1,256 @@
+use quote::quote;
+use syn::{DataEnum, Fields, Variant};
+
+use crate::attributes::{EnumAttributes, FieldAttributes, GroupDelimiter, VariantAttributes};
+use crate::keywords::{is_keyword_variant, keyword_of_variant};
+use crate::syntax::SyntaxTemplate;
+use crate::{field_locals, variant_with_locals};
+
+// generate the implementation of the quote::ToTokens trait for the enum. The implementation
+// emits the fields of the variant in order of declaration, so that the tokens can be parsed
+// back into the same variant by the implementation of the Parse trait. Example:
+//
+//    impl ::parse_variants::__private::quote::ToTokens for MyEnum {
+//        fn to_tokens(&self, tokens: &mut ::parse_variants::__private::proc_macro2::TokenStream) {
+//            match self {
+//                MyEnum::Pair { first: field_0, second: field_1 } => {
+//                    ::parse_variants::__private::quote::ToTokens::to_tokens(field_0, tokens);
+//                    ::parse_variants::__private::quote::ToTokens::to_tokens(field_1, tokens);
+//                }
+//                MyEnum::Ignored { .. } => {}
+//            }
+//        }
+//    }
+pub(crate) fn to_tokens_impl(
+    enum_ident: &syn::Ident,
+    enum_attributes: &[syn::Attribute],
+    data_enum: &DataEnum,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let enum_attributes = EnumAttributes::from_attributes(enum_attributes)?;
+    let mut match_arms = proc_macro2::TokenStream::new();
+    for variant in data_enum.variants.iter() {
+        match_arms.extend(variant_to_tokens(enum_ident, &enum_attributes, variant)?);
+    }
+
+    Ok(quote! {
+        impl ::parse_variants::__private::quote::ToTokens for #enum_ident {
+            fn to_tokens(&self, tokens : &mut ::parse_variants::__private::proc_macro2::TokenStream) {
+                match self {
+                    #match_arms
+                }
+            }
+        }
+    })
+}
+
+// helper function to generate the match arm that emits the tokens for the given variant.
+// Ignored variants don't emit any tokens, because their fields cannot be expected to implement
+// ToTokens. If the variant has a separator given by `#[parse(sep = ...)]`, the separator is
+// emitted between consecutive fields. If the separators were kept in a field marked with
+// `#[parse(separators)]`, the kept separators are emitted. Otherwise, a default constructed
+// separator is emitted. Variants with a syntax template emit the tokens of their template and
+// keyword variants emit their keyword or the spelling they were parsed from.
+// The fields of a variant with a delimiter are emitted inside a group with that delimiter.
+// If the tokens of the variant were kept in a field marked `#[parse(tokens)]`, they are emitted instead.
+// The prefix and suffix of the enumeration are emitted around the tokens of each variant and
+// the tokens of the variant are emitted inside a group, if the enumeration is delimited.
+fn variant_to_tokens(
+    enum_ident: &syn::Ident,
+    enum_attributes: &EnumAttributes,
+    variant: &Variant,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let attributes = VariantAttributes::from_attributes(&variant.attrs)?;
+    let variant_name = &variant.ident;
+    if attributes.ignore {
+        let pattern = match variant.fields {
+            Fields::Named(_) => quote! {#enum_ident::#variant_name {..}},
+            Fields::Unnamed(_) => quote! {#enum_ident::#variant_name (..)},
+            Fields::Unit => quote! {#enum_ident::#variant_name},
+        };
+        return Ok(quote! {#pattern => {}});
+    }
+
+    let locals = field_locals(&variant.fields);
+    let field_attributes = variant
+        .fields
+        .iter()
+        .map(|field| FieldAttributes::from_attributes(&field.attrs))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let emit_fields = match attributes.syntax {
+        None if is_keyword_variant(variant, &attributes) => {
+            let keyword = keyword_of_variant(variant, enum_attributes, &attributes)?;
+            quote! {
+                ::parse_variants::__private::emit_keyword(tokens, #keyword);
+            }
+        }
+        Some(ref syntax) => {
+            let parsed: Vec<_> = field_attributes
+                .iter()
+                .map(|attributes_of_field| {
+                    !attributes_of_field.delimiter
+                        && !attributes_of_field.spelling
+                        && !attributes_of_field.captures_variant()
+                })
+                .collect();
+            SyntaxTemplate::from_variant(syntax, variant, &parsed)?.to_tokens_statements()
+        }
+        None => emit_fields(&attributes, &locals, &field_attributes),
+    };
+
+    // if the spelling that was parsed is kept, it is emitted instead of the canonical spelling,
+    // unless the variant is marked `#[parse(canonical)]`
+    let spelling_local = locals
+        .iter()
+        .zip(field_attributes.iter())
+        .find(|(_, attributes_of_field)| attributes_of_field.spelling)
+        .map(|(local, _)| local);
+    let emit_fields = match spelling_local {
+        Some(spelling_local) if attributes.canonical => quote! {
+            let _ = #spelling_local;
+            #emit_fields
+        },
+        Some(spelling_local) => quote! {
+            ::parse_variants::__private::quote::ToTokens::to_tokens(#spelling_local, tokens);
+        },
+        None => emit_fields,
+    };
+
+    // the fields that capture the whole variant are not emitted
+    let capture_locals = locals
+        .iter()
+        .zip(field_attributes.iter())
+        .filter(|(_, attributes_of_field)| attributes_of_field.captures_variant())
+        .map(|(local, _)| local);
+    let emit_fields = quote! {
+        #(let _ = #capture_locals;)*
+        #emit_fields
+    };
+
+    // the fields of a delimited variant are emitted inside the group. If the delimiter token
+    // was stored, it is used for the group, so that its span is kept.
+    let emit_fields = match attributes.delimiter {
+        Some(delimiter) => {
+            let delimiter_local = locals
+                .iter()
+                .zip(field_attributes.iter())
+                .find(|(_, attributes_of_field)| attributes_of_field.delimiter)
+                .map(|(local, _)| local);
+            match delimiter_local {
+                Some(delimiter_local) => quote! {
+                    #delimiter_local.surround(tokens, |tokens| {
+                        #emit_fields
+                    });
+                },
+                None => emit_group(delimiter, emit_fields),
+            }
+        }
+        None => emit_fields,
+    };
+
+    // the prefix and suffix of the enumeration are not kept, so we emit default constructed tokens
+    let emit_prefix = enum_attributes.prefix.as_ref().map(|prefix| {
+        quote! {
+            ::parse_variants::__private::quote::ToTokens::to_tokens(&<#prefix as ::std::default::Default>::default(), tokens);
+        }
+    });
+    let emit_suffix = enum_attributes.suffix.as_ref().map(|suffix| {
+        quote! {
+            ::parse_variants::__private::quote::ToTokens::to_tokens(&<#suffix as ::std::default::Default>::default(), tokens);
+        }
+    });
+
+    // if the tokens of the variant are kept, they are emitted instead of the fields
+    let tokens_local = locals
+        .iter()
+        .zip(field_attributes.iter())
+        .find(|(_, attributes_of_field)| attributes_of_field.tokens)
+        .map(|(local, _)| local);
+    let emit_fields = match tokens_local {
+        Some(tokens_local) => {
+            let other_locals = locals.iter().filter(|local| *local != tokens_local);
+            quote! {
+                #(let _ = #other_locals;)*
+                ::parse_variants::__private::quote::ToTokens::to_tokens(#tokens_local, tokens);
+            }
+        }
+        None => emit_fields,
+    };
+
+    let emit_fields = match enum_attributes.delimited {
+        Some(delimited) => emit_group(delimited, emit_fields),
+        None => emit_fields,
+    };
+
+    let pattern = variant_with_locals(enum_ident, variant);
+    Ok(quote! {
+        #pattern => {
+            #emit_prefix
+            #emit_fields
+            #emit_suffix
+        }
+    })
+}
+
+// helper function to wrap the given statements, such that they emit into a group with the given delimiter
+fn emit_group(
+    delimiter: GroupDelimiter,
+    emit_content: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let group_delimiter = delimiter.group_delimiter();
+    quote! {
+        ::parse_variants::__private::emit_group(
+            tokens,
+            ::parse_variants::__private::proc_macro2::Delimiter::#group_delimiter,
+            |tokens| {
+                #emit_content
+            },
+        );
+    }
+}
+
+// helper function to generate the statements that emit the fields of a variant without
+// a syntax template. The separators field, the delimiter field and the fields that capture the
+// whole variant are not emitted themselves.
+fn emit_fields(
+    attributes: &VariantAttributes,
+    locals: &[syn::Ident],
+    field_attributes: &[FieldAttributes],
+) -> proc_macro2::TokenStream {
+    let separators_local = locals
+        .iter()
+        .zip(field_attributes.iter())
+        .find(|(_, attributes_of_field)| attributes_of_field.separators)
+        .map(|(local, _)| local);
+
+    let mut emit_fields = proc_macro2::TokenStream::new();
+    let parsed_locals = locals
+        .iter()
+        .zip(field_attributes.iter())
+        .filter(|(_, attributes_of_field)| {
+            !attributes_of_field.separators
+                && !attributes_of_field.delimiter
+                && !attributes_of_field.captures_variant()
+        })
+        .map(|(local, _)| local);
+    for (index, local) in parsed_locals.enumerate() {
+        if let (Some(ref separator), true) = (&attributes.separator, index > 0) {
+            let separator_index = index - 1;
+            emit_fields.extend(match separators_local {
+                Some(separators_local) => quote! {
+                    match #separators_local.get(#separator_index) {
+                        Some(separator) => ::parse_variants::__private::quote::ToTokens::to_tokens(separator, tokens),
+                        None => ::parse_variants::__private::quote::ToTokens::to_tokens(&<#separator as ::std::default::Default>::default(), tokens),
+                    }
+                },
+                None => quote! {
+                    ::parse_variants::__private::quote::ToTokens::to_tokens(&<#separator as ::std::default::Default>::default(), tokens);
+                },
+            });
+        }
+        emit_fields.extend(quote! {
+            ::parse_variants::__private::quote::ToTokens::to_tokens(#local, tokens);
+        });
+    }
+    emit_fields
+}
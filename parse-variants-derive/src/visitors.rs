@@ -0,0 +1,376 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{Fields, Item, ItemMod, Member};
+
+use crate::field_locals;
+use crate::keywords::RenameRule;
+
+// generate the traits `Visit<'ast>`, `VisitMut` and `Fold` for the enumerations and structs of an
+// inline module marked `#[parse_variants::visitors]`, in the style of `syn::visit`, `syn::visit_mut`
+// and `syn::fold`. Each type of the module gets a trait method that is named after the type in snake case
+// and defaults to a free function of the same name, which recurses into the fields of the type. Fields
+// are only visited if their type is a type of the module, possibly inside a `Box`, `Option`, `Vec` or
+// `Punctuated`. All other fields, like the syntax tree types of syn, are left alone. Example:
+//
+//    pub trait Visit<'ast> {
+//        fn visit_statement(&mut self, node: &'ast Statement) {
+//            visit_statement(self, node)
+//        }
+//    }
+//    pub fn visit_statement<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Statement) {
+//        match node {
+//            Statement::Block { 0: field_0, .. } => {
+//                for node_0 in field_0 {
+//                    v.visit_statement(node_0);
+//                }
+//            }
+//            Statement::Expression { .. } => {}
+//        }
+//    }
+//
+// `VisitMut` and `Fold` look the same, with the methods `visit_statement_mut(&mut self, node: &mut Statement)`
+// and `fold_statement(&mut self, node: Statement) -> Statement`.
+pub(crate) fn visitors_impl(mut item_mod: ItemMod) -> Result<TokenStream, syn::Error> {
+    let items = match item_mod.content {
+        Some((_, ref mut items)) => items,
+        None => {
+            return Err(syn::Error::new(
+                item_mod.span(),
+                "expected inline module: the visitors can only be generated for the types of a module with a body",
+            ))
+        }
+    };
+
+    let mut types = Vec::new();
+    for item in items.iter() {
+        let (ident, generics, vis, shape) = match item {
+            Item::Enum(item_enum) => (
+                &item_enum.ident,
+                &item_enum.generics,
+                &item_enum.vis,
+                Shape::Enum(
+                    item_enum
+                        .variants
+                        .iter()
+                        .map(|variant| (&variant.ident, &variant.fields))
+                        .collect(),
+                ),
+            ),
+            Item::Struct(item_struct) => (
+                &item_struct.ident,
+                &item_struct.generics,
+                &item_struct.vis,
+                Shape::Struct(&item_struct.fields),
+            ),
+            _ => continue,
+        };
+        if !generics.params.is_empty() {
+            return Err(syn::Error::new(
+                generics.span(),
+                "generic type: the visitors do not support generic types",
+            ));
+        }
+        types.push(VisitedType { ident, vis, shape });
+    }
+
+    let vis = match types.first() {
+        Some(first) => first.vis,
+        None => {
+            return Err(syn::Error::new(
+                item_mod.ident.span(),
+                "no types: the module must contain at least one enumeration or struct",
+            ))
+        }
+    };
+    // the traits mention all types of the module, so they can't be more visible than the types
+    let vis_name = quote! {#vis}.to_string();
+    if let Some(other) = types.iter().find(|other| {
+        let other_vis = other.vis;
+        quote! {#other_vis}.to_string() != vis_name
+    }) {
+        return Err(syn::Error::new(
+            other.ident.span(),
+            "mismatched visibility: all types of the module must have the same visibility as the generated traits",
+        ));
+    }
+
+    let names: Vec<_> = types.iter().map(|visited| visited.ident).collect();
+    let mut visit_methods = TokenStream::new();
+    let mut visit_mut_methods = TokenStream::new();
+    let mut fold_methods = TokenStream::new();
+    let mut functions = TokenStream::new();
+    for visited in types.iter() {
+        let ident = visited.ident;
+        let snake_name = RenameRule::Snake.apply(&ident.unraw().to_string());
+        let visit = format_ident!("visit_{}", snake_name);
+        let visit_mut = format_ident!("visit_{}_mut", snake_name);
+        let fold = format_ident!("fold_{}", snake_name);
+
+        let mut visit_arms = TokenStream::new();
+        let mut visit_mut_arms = TokenStream::new();
+        let mut fold_arms = TokenStream::new();
+        for (path, fields) in visited.shape.paths(ident) {
+            let members: Vec<_> = fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| member_of(index, field))
+                .collect();
+            let locals = field_locals(fields);
+            let mut visited_members = Vec::new();
+            let mut visited_locals = Vec::new();
+            let mut visit_fields = TokenStream::new();
+            let mut visit_mut_fields = TokenStream::new();
+            let mut folded_fields = Vec::new();
+            for ((field, member), local) in fields.iter().zip(&members).zip(&locals) {
+                match FieldType::of(&field.ty, &names) {
+                    Some(field_type) => {
+                        visited_members.push(member);
+                        visited_locals.push(local);
+                        visit_fields.extend(field_type.visit(quote! {#local}, Visitor::Visit, 0));
+                        visit_mut_fields.extend(field_type.visit(
+                            quote! {#local},
+                            Visitor::VisitMut,
+                            0,
+                        ));
+                        folded_fields.push(field_type.fold(quote! {#local}, 0));
+                    }
+                    None => folded_fields.push(quote! {#local}),
+                }
+            }
+            let visit_pattern = quote! {#path { #(#visited_members: #visited_locals,)* .. }};
+            visit_arms.extend(quote! {#visit_pattern => { #visit_fields }});
+            visit_mut_arms.extend(quote! {#visit_pattern => { #visit_mut_fields }});
+            fold_arms.extend(quote! {
+                #path { #(#members: #locals),* } => #path { #(#members: #folded_fields),* },
+            });
+        }
+
+        let visit_doc = format!("Visit a [`{}`] by reference.", ident.unraw());
+        let visit_mut_doc = format!("Visit a [`{}`] by mutable reference.", ident.unraw());
+        let fold_doc = format!("Fold a [`{}`] into a new one.", ident.unraw());
+        visit_methods.extend(quote! {
+            #[doc = #visit_doc]
+            fn #visit(&mut self, node: &'ast #ident) {
+                #visit(self, node)
+            }
+        });
+        visit_mut_methods.extend(quote! {
+            #[doc = #visit_mut_doc]
+            fn #visit_mut(&mut self, node: &mut #ident) {
+                #visit_mut(self, node)
+            }
+        });
+        fold_methods.extend(quote! {
+            #[doc = #fold_doc]
+            fn #fold(&mut self, node: #ident) -> #ident {
+                #fold(self, node)
+            }
+        });
+        functions.extend(quote! {
+            #[doc = #visit_doc]
+            #[allow(unused_variables)]
+            #vis fn #visit<'ast, V: Visit<'ast> + ?::std::marker::Sized>(v: &mut V, node: &'ast #ident) {
+                match node {
+                    #visit_arms
+                }
+            }
+
+            #[doc = #visit_mut_doc]
+            #[allow(unused_variables)]
+            #vis fn #visit_mut<V: VisitMut + ?::std::marker::Sized>(v: &mut V, node: &mut #ident) {
+                match node {
+                    #visit_mut_arms
+                }
+            }
+
+            #[doc = #fold_doc]
+            #[allow(unused_variables)]
+            #vis fn #fold<F: Fold + ?::std::marker::Sized>(f: &mut F, node: #ident) -> #ident {
+                match node {
+                    #fold_arms
+                }
+            }
+        });
+    }
+
+    let generated = quote! {
+        /// Traverse the types of this module by reference.
+        #vis trait Visit<'ast> {
+            #visit_methods
+        }
+
+        /// Traverse the types of this module by mutable reference.
+        #vis trait VisitMut {
+            #visit_mut_methods
+        }
+
+        /// Traverse the types of this module by value, building new values of the types.
+        #vis trait Fold {
+            #fold_methods
+        }
+    };
+    items.extend(syn::parse2::<syn::File>(quote! {#generated #functions})?.items);
+    Ok(quote! {#item_mod})
+}
+
+// an enumeration or struct of the module
+struct VisitedType<'a> {
+    ident: &'a syn::Ident,
+    vis: &'a syn::Visibility,
+    shape: Shape<'a>,
+}
+
+enum Shape<'a> {
+    Enum(Vec<(&'a syn::Ident, &'a Fields)>),
+    Struct(&'a Fields),
+}
+
+impl<'a> Shape<'a> {
+    // the paths to construct and destructure the variants of an enumeration or the struct, together
+    // with their fields, e.g. `MyEnum::Variant` or `MyStruct`
+    fn paths(&self, ident: &syn::Ident) -> Vec<(TokenStream, &'a Fields)> {
+        match self {
+            Shape::Enum(variants) => variants
+                .iter()
+                .map(|(variant, fields)| (quote! {#ident::#variant}, *fields))
+                .collect(),
+            Shape::Struct(fields) => vec![(quote! {#ident}, *fields)],
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Visitor {
+    Visit,
+    VisitMut,
+}
+
+// the type of a field that contains a type of the module
+enum FieldType<'a> {
+    Visited(&'a syn::Ident),
+    Box(Box<FieldType<'a>>),
+    Option(Box<FieldType<'a>>),
+    Vec(Box<FieldType<'a>>),
+    Punctuated(Box<FieldType<'a>>),
+}
+
+impl<'a> FieldType<'a> {
+    // classify the type of a field. The types of the module are referred to by their name alone, so
+    // a type like `syn::Expr` is not confused with a type `Expr` of the module.
+    // # Returns
+    // The field type or None, if the field does not contain a type of the module.
+    fn of(field_type: &syn::Type, names: &[&'a syn::Ident]) -> Option<Self> {
+        let path = match field_type {
+            syn::Type::Path(type_path) if type_path.qself.is_none() => &type_path.path,
+            _ => return None,
+        };
+        let segment = path.segments.last()?;
+        let arguments = match segment.arguments {
+            syn::PathArguments::None => {
+                return names
+                    .iter()
+                    .copied()
+                    .find(|name| path.is_ident(*name))
+                    .map(FieldType::Visited);
+            }
+            syn::PathArguments::AngleBracketed(ref arguments) => &arguments.args,
+            syn::PathArguments::Parenthesized(_) => return None,
+        };
+        let inner = match arguments.first() {
+            Some(syn::GenericArgument::Type(inner)) => Box::new(FieldType::of(inner, names)?),
+            _ => return None,
+        };
+        match segment.ident.to_string().as_str() {
+            "Box" if arguments.len() == 1 => Some(FieldType::Box(inner)),
+            "Option" if arguments.len() == 1 => Some(FieldType::Option(inner)),
+            "Vec" if arguments.len() == 1 => Some(FieldType::Vec(inner)),
+            "Punctuated" if arguments.len() == 2 => Some(FieldType::Punctuated(inner)),
+            _ => None,
+        }
+    }
+
+    // generate the statements that visit the value of the field given by reference
+    fn visit(&self, value: TokenStream, visitor: Visitor, depth: usize) -> TokenStream {
+        let node = format_ident!("node_{}", depth);
+        match self {
+            FieldType::Visited(ident) => {
+                let snake_name = RenameRule::Snake.apply(&ident.unraw().to_string());
+                let method = match visitor {
+                    Visitor::Visit => format_ident!("visit_{}", snake_name),
+                    Visitor::VisitMut => format_ident!("visit_{}_mut", snake_name),
+                };
+                quote! {v.#method(#value);}
+            }
+            FieldType::Box(inner) => {
+                let unboxed = match visitor {
+                    Visitor::Visit => quote! {&**#value},
+                    Visitor::VisitMut => quote! {&mut **#value},
+                };
+                inner.visit(unboxed, visitor, depth)
+            }
+            FieldType::Option(inner) => {
+                let visit_inner = inner.visit(quote! {#node}, visitor, depth + 1);
+                quote! {
+                    if let ::std::option::Option::Some(#node) = #value {
+                        #visit_inner
+                    }
+                }
+            }
+            FieldType::Vec(inner) | FieldType::Punctuated(inner) => {
+                let visit_inner = inner.visit(quote! {#node}, visitor, depth + 1);
+                quote! {
+                    for #node in #value {
+                        #visit_inner
+                    }
+                }
+            }
+        }
+    }
+
+    // generate the expression that folds the value of the field given by value
+    fn fold(&self, value: TokenStream, depth: usize) -> TokenStream {
+        let node = format_ident!("node_{}", depth);
+        match self {
+            FieldType::Visited(ident) => {
+                let snake_name = RenameRule::Snake.apply(&ident.unraw().to_string());
+                let method = format_ident!("fold_{}", snake_name);
+                quote! {f.#method(#value)}
+            }
+            FieldType::Box(inner) => {
+                let fold_inner = inner.fold(quote! {(*#value)}, depth);
+                quote! {::std::boxed::Box::new(#fold_inner)}
+            }
+            FieldType::Option(inner) => {
+                let fold_inner = inner.fold(quote! {#node}, depth + 1);
+                quote! {#value.map(|#node| #fold_inner)}
+            }
+            FieldType::Vec(inner) => {
+                let fold_inner = inner.fold(quote! {#node}, depth + 1);
+                quote! {#value.into_iter().map(|#node| #fold_inner).collect()}
+            }
+            FieldType::Punctuated(inner) => {
+                let fold_inner = inner.fold(quote! {#node}, depth + 1);
+                quote! {
+                    #value
+                        .into_pairs()
+                        .map(|pair| {
+                            let (#node, punct) = pair.into_tuple();
+                            ::syn::punctuated::Pair::new(#fold_inner, punct)
+                        })
+                        .collect()
+                }
+            }
+        }
+    }
+}
+
+// helper function to give the member of a field, which can be used in braced patterns and expressions
+// for all kinds of fields, e.g. `MyEnum::Variant { 0: field_0 }` for tuple like variants
+fn member_of(index: usize, field: &syn::Field) -> Member {
+    match field.ident {
+        Some(ref ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(index.into()),
+    }
+}
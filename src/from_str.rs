@@ -0,0 +1,37 @@
+// helpers for the code that is generated for enumerations marked `#[parse(from_str)]`
+use syn::parse::Parse;
+
+// parse the string as the given type, like `syn::parse_str`. Since the error of a string has no
+// source file to point at, the line and column of the error are rendered into its message,
+// e.g. "line 1, column 5: expected `=`". Combined errors are rendered one by one.
+#[cfg(feature = "span-locations")]
+pub fn parse_str<T: Parse>(source: &str) -> syn::Result<T> {
+    syn::parse_str(source).map_err(|error| {
+        error
+            .into_iter()
+            .map(|error| {
+                let start = error.span().start();
+                syn::Error::new(
+                    error.span(),
+                    format!(
+                        "line {}, column {}: {}",
+                        start.line,
+                        start.column + 1,
+                        error
+                    ),
+                )
+            })
+            .reduce(|mut combined, error| {
+                combined.combine(error);
+                combined
+            })
+            // an error always has at least one message
+            .unwrap_or_else(|| syn::Error::new(proc_macro2::Span::call_site(), "parse error"))
+    })
+}
+
+// without the locations of the spans, the error is returned as it is
+#[cfg(not(feature = "span-locations"))]
+pub fn parse_str<T: Parse>(source: &str) -> syn::Result<T> {
+    syn::parse_str(source)
+}
@@ -0,0 +1,108 @@
+// helpers for the code that is generated for keyword variants, i.e. unit variants that are
+// parsed from an identifier. A keyword consists of identifiers that may be joined by `-`,
+// like `fast-forward`, which is why it cannot be matched as a single identifier.
+use proc_macro2::{Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+use syn::parse::ParseStream;
+
+// parse the keyword from the input and drop it. If `case_insensitive` is true, the
+// identifiers of the keyword are compared regardless of their case.
+pub fn parse_keyword(input: ParseStream, keyword: &str, case_insensitive: bool) -> syn::Result<()> {
+    let matches = |ident: &Ident, word: &str| {
+        if case_insensitive {
+            ident.to_string().to_lowercase() == word.to_lowercase()
+        } else {
+            ident == word
+        }
+    };
+
+    input.step(|cursor| {
+        let mut rest = *cursor;
+        for (index, word) in keyword.split('-').enumerate() {
+            if index > 0 {
+                match rest.punct() {
+                    Some((punct, next)) if punct.as_char() == '-' => rest = next,
+                    _ => return Err(cursor.error(format!("expected `{}`", keyword))),
+                }
+            }
+            match rest.ident() {
+                Some((ident, next)) if matches(&ident, word) => rest = next,
+                _ => return Err(cursor.error(format!("expected `{}`", keyword))),
+            }
+        }
+        Ok(((), rest))
+    })
+}
+
+// emit the keyword as identifiers joined by `-`
+pub fn emit_keyword(tokens: &mut TokenStream, keyword: &str) {
+    for (index, word) in keyword.split('-').enumerate() {
+        if index > 0 {
+            tokens.extend(std::iter::once(TokenTree::Punct(Punct::new(
+                '-',
+                Spacing::Alone,
+            ))));
+        }
+        tokens.extend(std::iter::once(TokenTree::Ident(Ident::new(
+            word,
+            Span::call_site(),
+        ))));
+    }
+}
+
+// give an error that suggests the keyword that is closest to the identifier at the start of the input,
+// e.g. "unknown unit `meterz`, did you mean `meters`?". The error is spanned to the identifier.
+// The enumeration is described by `description` in the error message. The keywords are given
+// together with whether they are matched regardless of their case.
+// # Returns
+// The error or `None` if the input does not start with an identifier or if no keyword is close
+// enough to the identifier to be a sensible suggestion.
+pub fn suggest_keyword(
+    input: ParseStream,
+    description: &str,
+    keywords: &[(&str, bool)],
+) -> Option<syn::Error> {
+    let (ident, _) = input.cursor().ident()?;
+    let unknown = ident.to_string();
+    // the distance ignores the case, so that `Meters` is close to `meters`
+    let (distance, (closest, case_insensitive)) = keywords
+        .iter()
+        .map(|keyword| {
+            let distance = edit_distance(&unknown.to_lowercase(), &keyword.0.to_lowercase());
+            (distance, keyword)
+        })
+        .min_by_key(|(distance, _)| *distance)?;
+
+    // we don't suggest the keyword itself and only suggest keywords that are reasonably close,
+    // which is within a third of the length of the keyword
+    let is_keyword = distance == 0 && (*case_insensitive || unknown == *closest);
+    if is_keyword || distance > std::cmp::max(1, closest.chars().count() / 3) {
+        return None;
+    }
+    Some(syn::Error::new(
+        ident.span(),
+        format!(
+            "unknown {} `{}`, did you mean `{}`?",
+            description, ident, closest
+        ),
+    ))
+}
+
+// the Levenshtein distance between the two strings, i.e. the minimal number of inserted,
+// deleted or substituted characters that turn one string into the other
+fn edit_distance(first: &str, second: &str) -> usize {
+    let second: Vec<char> = second.chars().collect();
+    // the distances from the current prefix of the first string to all prefixes of the second string
+    let mut distances: Vec<usize> = (0..=second.len()).collect();
+    for (row, first_char) in first.chars().enumerate() {
+        let mut diagonal = distances[0];
+        distances[0] = row + 1;
+        for (column, second_char) in second.iter().enumerate() {
+            let substitution = diagonal + usize::from(first_char != *second_char);
+            diagonal = distances[column + 1];
+            distances[column + 1] = substitution
+                .min(distances[column] + 1)
+                .min(distances[column + 1] + 1);
+        }
+    }
+    distances[second.len()]
+}
@@ -77,9 +77,596 @@
 /// ```
 /// This is why we have to pay attention to ordering the variants from least general to most general.
 ///
-/// ## Restrictions
-/// The enumeration cannot contain unit variants (i.e. without member fields), because there is no
-/// useful way to parse them.
+/// ## Parse Order and Priorities
+/// Sometimes the order of declaration is dictated by other concerns, e.g. because the enum
+/// derives `Ord` or because its discriminants matter. In that case, the order in which variants
+/// are tried can be changed with the `#[parse(priority = N)]` attribute on a variant, where `N`
+/// is a (possibly negative) integer. Variants with a higher priority are tried first.
+/// Variants without the attribute have priority `0`, and variants with equal priority are
+/// tried in order of declaration.
+/// ```
+/// # use assert2::let_assert;
+/// #[derive(parse_variants::Parse)]
+/// enum Number {
+///     Expression(syn::Expr),
+///     #[parse(priority = 1)]
+///     Identifier(syn::Ident),
+/// }
+/// # let_assert!(Ok(Number::Identifier(_)) = syn::parse_str::<Number>("x"));
+/// # let_assert!(Ok(Number::Expression(_)) = syn::parse_str::<Number>("1+x"));
+/// ```
+/// Here, the `Identifier` variant is tried before the `Expression` variant, so the tokens `x`
+/// are parsed as an identifier and `1+x` as an expression.
+///
+/// ## Ignoring Variants
+/// Variants can be excluded from parsing with the `#[parse(ignore)]` attribute. This is useful
+/// for variants that are only ever constructed in code, e.g. by later passes of a compiler.
+/// Ignored variants are never returned from parsing and their fields do not have to implement
+/// `syn::parse::Parse`.
+/// ```
+/// # use assert2::let_assert;
+/// struct TypeId(u64);
+///
+/// #[derive(parse_variants::Parse)]
+/// enum Type {
+///     Named(syn::Ident),
+///     #[parse(ignore)]
+///     Resolved(TypeId),
+/// }
+/// # let_assert!(Ok(Type::Named(_)) = syn::parse_str::<Type>("Foo"));
+/// ```
+/// At least one variant of the enumeration must not be ignored.
+///
+/// ## Lookahead
+/// Sometimes a variant can only be told apart from another variant by the tokens that follow it.
+/// The `#[parse(followed_by = T)]` attribute on a variant requires that the tokens after the
+/// fields of the variant start with the token `T`. Conversely, `#[parse(not_followed_by = T)]`
+/// requires that they don't. The following token is only peeked and not consumed.
+/// `not_followed_by` can be given multiple times. The tokens must be something that can be
+/// [peeked](https://docs.rs/syn/latest/syn/parse/struct.ParseBuffer.html#method.peek), e.g.
+/// `Token![=]`, `syn::Ident`, or custom keywords.
+/// ```
+/// # use assert2::let_assert;
+/// # use syn::Token;
+/// # use syn::punctuated::Punctuated;
+/// #[derive(parse_variants::Parse)]
+/// enum Argument {
+///     #[parse(not_followed_by = Token![=])]
+///     Flag(syn::Ident),
+///     Assignment(syn::Ident, Token![=], syn::Expr),
+/// }
+/// # let_assert!(Ok(args) = syn::parse::Parser::parse_str(Punctuated::<Argument, Token![,]>::parse_terminated, "verbose, level = 3"));
+/// # let_assert!([Argument::Flag(_), Argument::Assignment(..)] = &args.into_iter().collect::<Vec<_>>()[..]);
+/// ```
+/// Here, the arguments `verbose, level = 3` are parsed as a flag and an assignment.
+///
+/// ## Separators
+/// Variants whose fields are separated by the same token, e.g. by commas, can use the
+/// `#[parse(sep = T)]` attribute instead of declaring a field for every separator. The separator `T`
+/// is then parsed between every two consecutive fields and discarded. To keep the parsed separators,
+/// mark a field of type `Vec<T>` with `#[parse(separators)]`. This field is not parsed from the input
+/// but filled with the separators instead. This can be used to give the separators back when
+/// deriving [`ToTokens`](self::ToTokens).
+/// ```
+/// # use assert2::let_assert;
+/// # use syn::Token;
+/// #[derive(parse_variants::Parse)]
+/// enum Triple {
+///     #[parse(sep = Token![,])]
+///     Integers(syn::LitInt, syn::LitInt, syn::LitInt),
+///     #[parse(sep = Token![,])]
+///     Expressions {
+///         a: syn::Expr,
+///         b: syn::Expr,
+///         c: syn::Expr,
+///         #[parse(separators)]
+///         commas: Vec<Token![,]>,
+///     },
+/// }
+/// # let_assert!(Ok(Triple::Integers(..)) = syn::parse_str::<Triple>("1, 2, 3"));
+/// # let_assert!(Ok(Triple::Expressions{commas, ..}) = syn::parse_str::<Triple>("x, y + 1, z"));
+/// # assert_eq!(commas.len(), 2);
+/// ```
+///
+/// ## Delimited Variants
+/// A variant that is wrapped as a whole in parentheses, brackets or braces can be marked with
+/// `#[parse(parenthesized)]`, `#[parse(bracketed)]` or `#[parse(braced)]`. Then the delimited group is
+/// parsed first and the fields of the variant are parsed from its content, which must be used up
+/// completely. The delimiter token (e.g. `syn::token::Paren`) and thus its span can be kept in a field
+/// marked `#[parse(delimiter)]`.
+/// ```
+/// # use assert2::let_assert;
+/// # use syn::Token;
+/// #[derive(parse_variants::Parse)]
+/// enum Value {
+///     #[parse(parenthesized, sep = Token![,])]
+///     Pair(syn::Expr, syn::Expr),
+///     #[parse(bracketed)]
+///     Array {
+///         #[parse(delimiter)]
+///         bracket: syn::token::Bracket,
+///         element: syn::Expr,
+///     },
+/// }
+/// let_assert!(Ok(Value::Pair(..)) = syn::parse_str::<Value>("(1, x + 2)"));
+/// let_assert!(Ok(Value::Array{..}) = syn::parse_str::<Value>("[x]"));
+/// let_assert!(Err(_) = syn::parse_str::<Value>("(1, 2, 3)"));
+/// ```
+///
+/// ## Common Prefix and Suffix
+/// If all variants of an enumeration start or end with the same token, this token can be given once
+/// on the enumeration with `#[parse(prefix = Token![#], suffix = Token![;])]`. The prefix is parsed once
+/// before any of the variants are tried and the suffix is parsed after the variant that was parsed.
+/// If the suffix is missing, the error points to the offending token.
+/// ```
+/// # use assert2::let_assert;
+/// # use syn::Token;
+/// #[derive(parse_variants::Parse)]
+/// #[parse(prefix = Token![#], suffix = Token![;])]
+/// enum Directive {
+///     Assignment(syn::Ident, Token![=], syn::Expr),
+///     Name(syn::Ident),
+/// }
+/// let_assert!(Ok(Directive::Assignment(..)) = syn::parse_str::<Directive>("#x = 1;"));
+/// let_assert!(Ok(Directive::Name(_)) = syn::parse_str::<Directive>("#dude;"));
+/// let_assert!(Err(error) = syn::parse_str::<Directive>("#the dude;"));
+/// assert_eq!(error.to_string(), "expected `;`");
+/// ```
+///
+/// ## Delimited Enumerations
+/// If all variants of an enumeration are wrapped in a group, the enumeration can be marked with
+/// `#[parse(delimited = parens)]`, `#[parse(delimited = brackets)]` or `#[parse(delimited = braces)]`.
+/// Then the group is parsed and the variants are tried on its content as usual. The content must be used
+/// up completely by the variant. A prefix of the enumeration is parsed before the group and a suffix after it.
+/// ```
+/// # use assert2::let_assert;
+/// #[derive(parse_variants::Parse)]
+/// #[parse(delimited = parens)]
+/// enum Mode {
+///     Speed(syn::Ident),
+///     Level(syn::LitInt),
+/// }
+/// let_assert!(Ok(Mode::Speed(_)) = syn::parse_str::<Mode>("(fast)"));
+/// let_assert!(Ok(Mode::Level(_)) = syn::parse_str::<Mode>("(3)"));
+/// let_assert!(Err(_) = syn::parse_str::<Mode>("fast"));
+/// ```
+///
+/// ## Commit Points
+/// By default, an error in any field of a variant makes the parser silently move on to the next
+/// variant. If none of the variants can be parsed, a generic error is returned. Often, a
+/// distinguishing token like `let` or `fn` makes clear which variant was meant. Marking
+/// such a field with `#[parse(commit)]` commits the parser to the variant once this field was
+/// parsed successfully: any error in the remaining fields of the variant is returned directly from
+/// `parse` and no other variants are tried. This is the *cut* operator known from PEG parsers and
+/// it gives much more precise error messages.
+/// ```
+/// # use assert2::let_assert;
+/// # use syn::Token;
+/// #[derive(parse_variants::Parse)]
+/// enum Statement {
+///     Let {
+///         #[parse(commit)]
+///         _let: Token![let],
+///         name: syn::Ident,
+///         _eq: Token![=],
+///         value: syn::Expr,
+///     },
+///     #[parse(fallback)]
+///     Verbatim(proc_macro2::TokenStream),
+/// }
+/// # let_assert!(Ok(Statement::Let{..}) = syn::parse_str::<Statement>("let x = 1"));
+/// # let_assert!(Ok(Statement::Verbatim(_)) = syn::parse_str::<Statement>("x = 1"));
+/// let_assert!(Err(error) = syn::parse_str::<Statement>("let x 1"));
+/// assert_eq!(error.to_string(), "expected `=`");
+/// ```
+///
+/// ## Fallback Variant
+/// A single variant can be marked with `#[parse(fallback)]` to capture tokens that cannot be parsed
+/// as any of the other variants, similar to `syn::Expr::Verbatim`. The fallback variant is always tried
+/// last, regardless of priorities. It must have exactly one field, whose type must be able to collect
+/// token trees (i.e. implement `FromIterator<proc_macro2::TokenTree>`), e.g. `proc_macro2::TokenStream`.
+/// The fallback variant collects all tokens up to the end of the input. Alternatively,
+/// `#[parse(fallback, terminator = Token![;])]` makes it collect tokens up to (but excluding) the
+/// given token. The fallback variant fails if it could not collect at least one token.
+/// ```
+/// # use assert2::let_assert;
+/// # use syn::Token;
+/// #[derive(parse_variants::Parse)]
+/// enum Statement {
+///     Assignment(syn::Ident, Token![=], syn::Expr, Token![;]),
+///     #[parse(fallback, terminator = Token![;])]
+///     Verbatim(proc_macro2::TokenStream),
+/// }
+/// # let_assert!(Ok(Statement::Assignment(..)) = syn::parse_str::<Statement>("x = 1;"));
+/// # let_assert!(Ok(Statement::Verbatim(_)) = syn::parse_str::<Statement>("some unknown syntax"));
+/// ```
+///
+/// ## Empty Input
+/// A unit variant marked `#[parse(empty)]` is parsed only if there are no tokens left, i.e. it
+/// succeeds where [`syn::parse::Nothing`] would, but only if the input is empty. Within a delimited
+/// enumeration, this is the case for an empty group. Nothing is emitted for the variant by the ToTokens derive.
+/// ```
+/// # use assert2::let_assert;
+/// # use syn::Token;
+/// #[derive(parse_variants::Parse)]
+/// enum Args {
+///     #[parse(empty)]
+///     None,
+///     Pair(syn::Ident, Token![,], syn::Ident),
+/// }
+/// let_assert!(Ok(Args::None) = syn::parse_str::<Args>(""));
+/// let_assert!(Ok(Args::Pair(..)) = syn::parse_str::<Args>("x, y"));
+/// ```
+///
+/// ## Syntax Templates
+/// Instead of declaring a field for every token, the syntax of a variant can be given as a
+/// `macro_rules!`-like template with `#[parse(syntax = "...")]`. The literal tokens of the template
+/// are matched and dropped, while the placeholders are parsed into the fields of the variant.
+/// Placeholders refer to named fields by name (`$name`) and to tuple fields by index (`$0`).
+/// Every field must appear exactly once in the template, which is checked at compile time.
+/// Like in `macro_rules!`, `$(...)*` and `$(...)+` repeat the enclosed part, optionally with
+/// a separator like in `$($args),*`. The fields inside the repetition collect their values in a `Vec`.
+/// An optional part is written as `$(...)?` and its fields must be `Option`s. A literal `$` is written `$$`.
+/// ```
+/// # use assert2::let_assert;
+/// #[derive(parse_variants::Parse)]
+/// enum Statement {
+///     #[parse(syntax = "let $name = $value ;")]
+///     Let { name: syn::Ident, value: syn::Expr },
+///     #[parse(syntax = "fn $0 ( $($1),* ) $(-> $2)?")]
+///     Function(syn::Ident, Vec<syn::Ident>, Option<syn::Type>),
+///     #[parse(syntax = "pass ;")]
+///     Pass,
+/// }
+/// let_assert!(Ok(Statement::Let{..}) = syn::parse_str::<Statement>("let x = 1 + y;"));
+/// let_assert!(Ok(Statement::Function(_, arguments, None)) = syn::parse_str::<Statement>("fn abide(the, dude)"));
+/// assert_eq!(arguments.len(), 2);
+/// let_assert!(Ok(Statement::Pass) = syn::parse_str::<Statement>("pass;"));
+/// ```
+/// Unlike in `macro_rules!`, the separator of a repetition must be a single token like `,` or `;`.
+/// Separators that consist of several punctuation characters, like `=>` or `::`, are rejected at compile time:
+/// ```compile_fail
+/// #[derive(parse_variants::Parse)]
+/// enum Path {
+///     #[parse(syntax = "$($0)::*")]
+///     Segments(Vec<syn::Ident>),
+/// }
+/// ```
+///
+/// ## Keyword Variants
+/// Unit variants (i.e. without member fields) are parsed from a keyword, which is the name of the variant
+/// by default. The keywords of all variants can be derived from their names with
+/// `#[parse(rename_all = "...")]` on the enumeration, which accepts the same rules as serde: `"lowercase"`,
+/// `"UPPERCASE"`, `"PascalCase"`, `"camelCase"`, `"snake_case"`, `"SCREAMING_SNAKE_CASE"`, `"kebab-case"`
+/// and `"SCREAMING-KEBAB-CASE"`. A single variant can be given its keyword with `#[parse(rename = "...")]`.
+/// Keywords consist of identifiers (including Rust keywords like `type`) that may be joined by `-`.
+/// Marking the enumeration or a variant with `#[parse(case_insensitive)]` matches the keywords
+/// regardless of their case.
+/// ```
+/// # use assert2::let_assert;
+/// #[derive(parse_variants::Parse)]
+/// #[parse(rename_all = "kebab-case", case_insensitive)]
+/// enum Level {
+///     Debug,
+///     WarnOnce,
+///     #[parse(rename = "err")]
+///     Error,
+/// }
+/// let_assert!(Ok(Level::Debug) = syn::parse_str::<Level>("debug"));
+/// let_assert!(Ok(Level::Debug) = syn::parse_str::<Level>("Debug"));
+/// let_assert!(Ok(Level::WarnOnce) = syn::parse_str::<Level>("warn-once"));
+/// let_assert!(Ok(Level::Error) = syn::parse_str::<Level>("err"));
+/// ```
+///
+/// If none of the variants can be parsed and the input starts with an identifier that is close to one
+/// of the keywords, the error suggests the closest keyword instead of giving the generic error.
+/// The error is spanned to the misspelled identifier.
+/// ```
+/// # use assert2::let_assert;
+/// #[derive(parse_variants::Parse)]
+/// #[parse(rename_all = "lowercase")]
+/// enum LengthUnit {
+///     Meters,
+///     Feet,
+/// }
+/// let_assert!(Err(error) = syn::parse_str::<LengthUnit>("meterz"));
+/// assert_eq!(error.to_string(), "unknown length unit `meterz`, did you mean `meters`?");
+/// ```
+///
+/// ## Aliases
+/// Keyword variants and variants with a syntax template without placeholders can accept alternative
+/// spellings given by `#[parse(alias = "...")]`, which may be repeated. For syntax templates, each alias is
+/// another template. Such a variant may keep the tokens of the spelling it was parsed from in a single field
+/// marked `#[parse(spelling)]`, whose type must be able to collect token trees (e.g. `proc_macro2::TokenStream`).
+/// The ToTokens derive emits the kept spelling, unless the variant is marked `#[parse(canonical)]`. Then it
+/// always emits the canonical spelling, i.e. the keyword or the syntax template itself.
+/// ```
+/// # use assert2::let_assert;
+/// #[derive(parse_variants::Parse)]
+/// #[parse(rename_all = "lowercase")]
+/// enum LengthUnit {
+///     #[parse(alias = "m", alias = "metre")]
+///     Meters,
+///     #[parse(alias = "ft")]
+///     Feet(#[parse(spelling)] proc_macro2::TokenStream),
+/// }
+/// let_assert!(Ok(LengthUnit::Meters) = syn::parse_str::<LengthUnit>("metre"));
+/// let_assert!(Ok(LengthUnit::Feet(spelling)) = syn::parse_str::<LengthUnit>("ft"));
+/// assert_eq!(spelling.to_string(), "ft");
+/// ```
+///
+/// ## Span Fields
+/// A field of type [`proc_macro2::Span`] that is marked `#[parse(span)]` is not parsed, but holds the span of
+/// the tokens that the variant was parsed from (without the common prefix and suffix of the enumeration),
+/// i.e. the span from the first to the last token of the variant. Where spans cannot be joined, it is the span of
+/// the first token. This lets errors in later passes point to the whole variant. Span fields are not emitted by the
+/// ToTokens derive. Note that enumerations that implement ToTokens already implement [`syn::spanned::Spanned`].
+/// ```
+/// # use assert2::let_assert;
+/// # use syn::Token;
+/// #[derive(parse_variants::Parse)]
+/// enum Statement {
+///     Assignment {
+///         name: syn::Ident,
+///         eq: Token![=],
+///         value: syn::Expr,
+///         #[parse(span)]
+///         span: proc_macro2::Span,
+///     },
+/// }
+/// let_assert!(Ok(Statement::Assignment { span, .. }) = syn::parse_str("x = 1 + 2"));
+/// assert_eq!(span.end().column, 9);
+/// ```
+///
+/// ## Token Fields
+/// A field marked `#[parse(tokens)]` is not parsed, but keeps exactly the tokens that the variant was parsed from
+/// (without the common prefix and suffix of the enumeration). Its type must be able to collect token trees, e.g.
+/// [`proc_macro2::TokenStream`]. The ToTokens derive emits the kept tokens instead of the fields of the variant,
+/// which makes re-emitting the variant lossless.
+/// ```
+/// # use assert2::let_assert;
+/// # use syn::Token;
+/// #[derive(parse_variants::Parse)]
+/// enum Statement {
+///     Assignment(syn::Ident, Token![=], syn::Expr, #[parse(tokens)] proc_macro2::TokenStream),
+/// }
+/// let_assert!(Ok(Statement::Assignment(.., raw)) = syn::parse_str("x = (1 + 2) * 3"));
+/// assert_eq!(raw.to_string(), "x = (1 + 2) * 3");
+/// ```
+///
+/// ## Deprecated Variants
+/// A variant marked `#[parse(deprecated = "...")]` is still parsed, but each successful parse records a
+/// [`Warning`] with the given message that is spanned to the start of the variant. Since there is no stable
+/// way for a procedural macro to emit warnings, the warnings are collected per thread and can be taken with
+/// [`warnings()`]. A macro can emit them with its output, where they show up as deprecation warnings.
+/// The warnings stay buffered until they are taken, up to [`MAX_WARNINGS`] per thread.
+/// ```
+/// # use assert2::let_assert;
+/// #[derive(parse_variants::Parse)]
+/// #[parse(rename_all = "lowercase")]
+/// enum LengthUnit {
+///     Metres,
+///     #[parse(deprecated = "use `metres` instead")]
+///     Meters,
+/// }
+/// let_assert!(Ok(LengthUnit::Meters) = syn::parse_str::<LengthUnit>("meters"));
+/// let warnings = parse_variants::warnings();
+/// assert_eq!(warnings[0].message(), "use `metres` instead");
+/// ```
+///
+/// ## Variant Kinds
+/// If the enumeration is marked `#[parse(kind)]`, the derive macro also generates a fieldless enumeration
+/// named after the enumeration with a `Kind` suffix. It has one variant for each variant of the enumeration
+/// (including ignored variants), implements `Copy`, `Eq`, `Hash` and `Debug` and lists all of its
+/// variants in order of declaration in `ALL`. The enumeration gets the methods `kind()` and
+/// `variant_name()`, which are handy for branching on or logging the variant that was parsed.
+/// ```
+/// # use syn::Token;
+/// #[derive(parse_variants::Parse)]
+/// #[parse(kind)]
+/// enum Setting {
+///     Assignment(syn::Ident, Token![=], syn::LitInt),
+///     Flag(syn::Ident),
+/// }
+/// let setting = syn::parse_str::<Setting>("level = 3").unwrap();
+/// assert_eq!(setting.kind(), SettingKind::Assignment);
+/// assert_eq!(setting.variant_name(), "Assignment");
+/// assert_eq!(SettingKind::ALL, &[SettingKind::Assignment, SettingKind::Flag]);
+/// ```
+///
+/// ## Accessors and Conversions
+/// If the enumeration is marked `#[parse(accessors)]`, the derive macro generates the methods `is_<variant>`,
+/// `as_<variant>` and `into_<variant>` for each variant, where `<variant>` is the name of the variant in snake case.
+/// The latter two give access to the field of the variant by reference or by value. For variants with more
+/// than one field, they give a tuple of the fields in order of declaration. Unit variants only have `is_<variant>`.
+/// If the enumeration is marked `#[parse(from)]`, `From` is implemented for the field type of each variant
+/// with a single field. Such field types must be distinct. The fallback variant and variants whose only field
+/// is not parsed, like a `#[parse(span)]`, `#[parse(tokens)]` or `#[parse(spelling)]` field, are not converted from.
+/// ```
+/// # use assert2::let_assert;
+/// #[derive(parse_variants::Parse)]
+/// #[parse(accessors, from)]
+/// enum Number {
+///     Identifier(syn::Ident),
+///     Literal(syn::LitInt),
+/// }
+/// let number = syn::parse_str::<Number>("x").unwrap();
+/// assert!(number.is_identifier());
+/// assert_eq!(number.as_identifier().unwrap(), "x");
+/// let_assert!(Err(number) = number.into_literal());
+///
+/// let literal: syn::LitInt = syn::parse_str("3").unwrap();
+/// let_assert!(Number::Literal(_) = Number::from(literal));
+/// ```
+///
+/// ## Constructors
+/// If the enumeration is marked `#[parse(constructors)]`, the derive macro generates a constructor for each variant,
+/// which is named after the variant in snake case. Only the semantic fields of the variant are parameters of the
+/// constructor. The token fields are default constructed, so that their spans are the call site. Token fields are
+/// fields of type `Token![...]` or `syn::token::...`, the separators and delimiter fields and fields marked
+/// `#[parse(default)]`, which is needed for custom keywords. Span fields are set to the call site and spelling
+/// fields get the canonical spelling of the variant. Variants with a `#[parse(tokens)]` field cannot be constructed,
+/// because the kept tokens would take the place of the other fields, so such enumerations cannot have constructors.
+/// Constructors whose names are keywords are raw identifiers, like `r#type` for `Type`, except for
+/// `crate`, `self` and `super`, which cannot be raw identifiers and get a trailing underscore, like `crate_`.
+/// ```
+/// # use syn::Token;
+/// use quote::ToTokens;
+/// mod kw {
+///     syn::custom_keyword!(meters);
+/// }
+///
+/// #[derive(parse_variants::Parse, parse_variants::ToTokens)]
+/// #[parse(constructors)]
+/// enum Length {
+///     Meters(syn::Expr, #[parse(default)] kw::meters),
+///     Sum(syn::LitInt, Token![+], syn::LitInt),
+/// }
+/// let length = Length::meters(syn::parse_str("1 + 2").unwrap());
+/// assert_eq!(length.to_token_stream().to_string(), "1 + 2 meters");
+/// ```
+/// ```compile_fail
+/// #[derive(parse_variants::Parse)]
+/// #[parse(constructors)]
+/// enum Statement {
+///     Assignment(syn::Ident, syn::Token![=], syn::Expr, #[parse(tokens)] proc_macro2::TokenStream),
+/// }
+/// ```
+///
+/// ## Respanning
+/// The [`Respan`](trait@crate::Respan) trait sets all spans of a value to the same span, which is useful when
+/// a parsed value is moved into generated code. The trait can be derived for an enumeration with
+/// `#[derive(parse_variants::Respan)]`, which respans all fields of the variant, so the types of the fields
+/// must implement `Respan`, too. Variants marked `#[parse(ignore)]` are left as they are.
+/// ```
+/// # use syn::Token;
+/// use parse_variants::Respan;
+/// use proc_macro2::Span;
+///
+/// #[derive(parse_variants::Parse, parse_variants::Respan)]
+/// enum Assignment {
+///     Value(syn::Ident, Token![=], syn::Expr),
+///     Flag(syn::Ident),
+/// }
+/// let mut assignment: Assignment = syn::parse_str("x = 1 + y").unwrap();
+/// assignment.respan(Span::call_site());
+/// ```
+///
+/// ## Visitors
+/// Larger syntax trees consist of many enumerations and structs that contain each other. The attribute
+/// `#[parse_variants::visitors]` on an inline module generates the traits `Visit<'ast>`, `VisitMut` and `Fold`
+/// for the enumerations and structs of the module, in the style of `syn::visit`, `syn::visit_mut` and `syn::fold`.
+/// Each type gets a trait method named after the type in snake case, e.g. `visit_statement`, `visit_statement_mut`
+/// and `fold_statement`, which defaults to the free function of the same name in the module. The free functions
+/// recurse into the fields of the type, so an implementation that overrides a method can call them to continue
+/// the traversal.
+/// * Fields are only visited if their type is a type of the module, referred to by its name alone, possibly
+///   inside a `Box`, `Option`, `Vec` or `Punctuated`. All other fields, like the syntax tree types of syn,
+///   are left alone.
+/// * The types must not be generic and must all have the same visibility, which is also the visibility of the
+///   generated traits and functions.
+/// ```
+/// #[parse_variants::visitors]
+/// mod ast {
+///     #[derive(parse_variants::Parse)]
+///     pub enum Condition {
+///         Not(syn::Token![!], Box<Condition>),
+///         Variable(syn::Ident),
+///     }
+/// }
+/// use ast::Visit;
+///
+/// struct Variables(Vec<String>);
+/// impl<'ast> Visit<'ast> for Variables {
+///     fn visit_condition(&mut self, node: &'ast ast::Condition) {
+///         if let ast::Condition::Variable(variable) = node {
+///             self.0.push(variable.to_string());
+///         }
+///         ast::visit_condition(self, node);
+///     }
+/// }
+/// let condition: ast::Condition = syn::parse_str("!!x").unwrap();
+/// let mut variables = Variables(Vec::new());
+/// variables.visit_condition(&condition);
+/// assert_eq!(variables.0, ["x"]);
+/// ```
+///
+/// ## Parsing Strings
+/// If the enumeration is marked `#[parse(from_str)]`, the derive macro implements [`std::str::FromStr`] by
+/// parsing the string like [`syn::parse_str`], so that the enumeration can be parsed from strings outside of
+/// procedural macros, e.g. in build scripts or command line tools. The error is a [`syn::Error`]. With the
+/// `span-locations` feature of this crate, its message starts with the line and column of the error in the
+/// string, e.g. `"line 2, column 3: unexpected token"`. The feature is opt-in, because it enables the
+/// `span-locations` feature of `proc-macro2` for the whole build.
+/// ```
+/// # use assert2::let_assert;
+/// # use syn::Token;
+/// #[derive(parse_variants::Parse)]
+/// #[parse(from_str)]
+/// enum Setting {
+///     Assignment(syn::Ident, Token![=], syn::LitInt),
+///     Flag(syn::Ident),
+/// }
+/// let_assert!(Ok(Setting::Assignment(..)) = "level = 3".parse::<Setting>());
+/// let_assert!(Err(error) = "level = 3\n  4".parse::<Setting>());
+/// assert!(error.to_string().ends_with("unexpected token"));
+/// ```
+///
+/// ## Serde
+/// With the `serde` feature of this crate, an enumeration marked `#[parse(serde)]` implements `serde::Deserialize`
+/// by deserializing a string and parsing it like [`std::str::FromStr`] (see above). It implements `serde::Serialize` by
+/// serializing its tokens as a string, so the enumeration must implement [`quote::ToTokens`], e.g. via the derive macro.
+/// This is handy for configuration files that contain small snippets of Rust-like syntax.
+/// ```
+/// # #[cfg(feature = "serde")]
+/// # fn main() {
+/// # use assert2::let_assert;
+/// # use syn::Token;
+/// #[derive(parse_variants::Parse, parse_variants::ToTokens)]
+/// #[parse(serde)]
+/// enum Setting {
+///     Assignment(syn::Ident, Token![=], syn::LitInt),
+///     Flag(syn::Ident),
+/// }
+/// let_assert!(Ok(Setting::Assignment(..)) = serde_json::from_str::<Setting>(r#""level = 3""#));
+/// # }
+/// # #[cfg(not(feature = "serde"))]
+/// # fn main() {}
+/// ```
+///
+/// ## Entry Points
+/// If the enumeration is marked `#[parse(entry_points)]`, the derive macro generates a function for each
+/// variant that is not ignored, which parses only that variant. The functions are named after the variants,
+/// e.g. `parse_fast_forward` for `FastForward`, and parse the common prefix and suffix and delimiters of
+/// the enumeration, too. Since no other variants are tried, they return the error of the variant itself
+/// instead of the generic error. If the enumeration is also marked `#[parse(kind)]`, the function
+/// `parse_variant` parses the variant of the given kind. The functions have the same visibility as the enumeration.
+/// ```
+/// # use assert2::let_assert;
+/// # use syn::Token;
+/// use syn::parse::Parser;
+///
+/// #[derive(parse_variants::Parse)]
+/// #[parse(kind, entry_points)]
+/// enum Setting {
+///     Assignment(syn::Ident, Token![=], syn::LitInt),
+///     Flag(syn::Ident),
+/// }
+/// let_assert!(Err(error) = Setting::parse_assignment.parse_str("verbose"));
+/// assert_eq!(error.to_string(), "expected `=`");
+/// let parse_flag = |input: syn::parse::ParseStream| Setting::parse_variant(SettingKind::Flag, input);
+/// let_assert!(Ok(Setting::Flag(_)) = parse_flag.parse_str("verbose"));
+/// ```
+/// The derive macro rejects variants whose entry points have the same name, like `HTTPServer` and `HttpServer`,
+/// and a variant named `Variant` together with `#[parse(kind)]`, whose entry point would clash with `parse_variant`:
+/// ```compile_fail
+/// #[derive(parse_variants::Parse)]
+/// #[parse(entry_points)]
+/// enum Server {
+///     HTTPServer(syn::Ident),
+///     HttpServer(syn::LitInt),
+/// }
+/// ```
 ///
 /// ## Example
 /// It is possible to write pretty complex parsers for variants. See this very silly example:
@@ -107,6 +694,77 @@
 /// This parses the tokens `16 + 12*length meters` as the first and `C++` as the second variant.
 pub use parse_variants_derive::Parse;
 
+/// A derive macro that implements [`quote::ToTokens`](https://docs.rs/quote/latest/quote/trait.ToTokens.html)
+/// for an enumeration, such that the tokens can be parsed back by the implementation
+/// derived with [`Parse`](self::Parse).
+///
+/// # Usage
+///
+/// **Attention** This crate requires that you have not renamed the `parse-variants` crate
+/// in your dependencies.
+///
+/// The derive macro understands the same `#[parse(...)]` attributes as the [`Parse`](self::Parse)
+/// derive macro. The generated implementation emits the tokens of the fields of the variant
+/// in order of declaration, so every field must implement `ToTokens`.
+/// * Fields that are not parsed from the input, such as a `#[parse(separators)]` field,
+///   are not emitted as such.
+/// * Separators given by `#[parse(sep = ...)]` are emitted between the fields. If the variant keeps
+///   the parsed separators in a `#[parse(separators)]` field, those separators are emitted. Otherwise
+///   (or if there are too few kept separators), a default constructed separator is emitted.
+/// * Ignored variants don't emit any tokens.
+///
+/// ```
+/// # use syn::Token;
+/// # use assert2::check;
+/// # use quote::ToTokens;
+/// #[derive(parse_variants::Parse, parse_variants::ToTokens)]
+/// enum Binding {
+///     Assignment(syn::Ident, Token![=], syn::Expr),
+///     Declaration(Token![let], syn::Ident),
+/// }
+/// let binding = syn::parse_str::<Binding>("x = 1 + y").unwrap();
+/// check!(binding.to_token_stream().to_string() == "x = 1 + y");
+/// ```
+pub use parse_variants_derive::ToTokens;
+
+/// Derive the [`Respan`](trait@crate::Respan) trait for an enumeration, which respans all
+/// fields of the variant. Variants marked `#[parse(ignore)]` are left as they are.
+pub use parse_variants_derive::Respan;
+
+/// Generate the traits `Visit<'ast>`, `VisitMut` and `Fold` for the enumerations and structs of an
+/// inline module, in the style of `syn::visit`. See the section on visitors in the
+/// documentation of the [`Parse`](derive@crate::Parse) derive macro.
+pub use parse_variants_derive::visitors;
+
+pub use respan::Respan;
+pub use warnings::{warnings, Warning, MAX_WARNINGS};
+
+// re-exports that are used by the generated code, so that users don't need to depend on
+// these crates themselves. This is not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::from_str::parse_str;
+    pub use crate::keywords::*;
+    pub use crate::syntax::*;
+    pub use crate::tokens::*;
+    pub use crate::warnings::{discard_warnings, record_warning, warnings_mark};
+    pub use proc_macro2;
+    pub use quote;
+    #[cfg(feature = "serde")]
+    pub use serde;
+}
+
+mod from_str;
+mod keywords;
+mod respan;
+mod syntax;
+mod tokens;
+mod warnings;
+
+// makes the `::parse_variants` paths in the generated code work inside this crate, too
+#[cfg(test)]
+extern crate self as parse_variants;
+
 #[cfg(test)]
 #[allow(clippy::large_enum_variant)]
 mod test;
@@ -0,0 +1,237 @@
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::punctuated::{Pair, Punctuated};
+
+/// Set every span of a value to the same span, e.g. when a parsed value is moved into generated code
+/// and should be attributed to a single location, for hygiene or for the location of errors.
+///
+/// The trait is implemented for the tokens of [`proc_macro2`], the literals and token types of [`syn`],
+/// a selection of syn's syntax tree types and the common containers. It can be derived for enumerations
+/// with the [`Respan`](derive@crate::Respan) derive macro, which respans all fields of the variant.
+///
+/// The syntax tree types [`syn::Expr`], [`syn::Type`] and [`syn::Path`] are respanned by visiting all of
+/// their spans, including those of their literals and of verbatim tokens like the tokens of macros.
+/// Since syn's visitors skip the punctuation and delimiters, these are respanned by parsing the respanned
+/// tokens again. Values that don't parse back from their tokens, e.g. an `Expr::Verbatim` with tokens that
+/// are not an expression, keep the spans of their punctuation.
+pub trait Respan {
+    /// Set all spans of the value to the given span.
+    fn respan(&mut self, span: Span);
+}
+
+impl Respan for Span {
+    fn respan(&mut self, span: Span) {
+        *self = span;
+    }
+}
+
+impl Respan for TokenStream {
+    fn respan(&mut self, span: Span) {
+        *self = std::mem::take(self)
+            .into_iter()
+            .map(|mut token_tree| {
+                token_tree.respan(span);
+                token_tree
+            })
+            .collect();
+    }
+}
+
+impl Respan for TokenTree {
+    fn respan(&mut self, span: Span) {
+        match self {
+            TokenTree::Group(group) => group.respan(span),
+            TokenTree::Ident(ident) => ident.set_span(span),
+            TokenTree::Punct(punct) => punct.set_span(span),
+            TokenTree::Literal(literal) => literal.set_span(span),
+        }
+    }
+}
+
+impl Respan for Group {
+    fn respan(&mut self, span: Span) {
+        let mut stream = self.stream();
+        stream.respan(span);
+        *self = Group::new(self.delimiter(), stream);
+        self.set_span(span);
+    }
+}
+
+impl Respan for proc_macro2::Ident {
+    fn respan(&mut self, span: Span) {
+        self.set_span(span);
+    }
+}
+
+impl Respan for proc_macro2::Punct {
+    fn respan(&mut self, span: Span) {
+        self.set_span(span);
+    }
+}
+
+impl Respan for proc_macro2::Literal {
+    fn respan(&mut self, span: Span) {
+        self.set_span(span);
+    }
+}
+
+impl Respan for syn::Lifetime {
+    fn respan(&mut self, span: Span) {
+        self.apostrophe = span;
+        self.ident.set_span(span);
+    }
+}
+
+impl Respan for syn::LitBool {
+    fn respan(&mut self, span: Span) {
+        self.span = span;
+    }
+}
+
+// the literals of syn can set their span themselves
+macro_rules! respan_literals {
+    ($($literal:ty),*) => {
+        $(
+            impl Respan for $literal {
+                fn respan(&mut self, span: Span) {
+                    self.set_span(span);
+                }
+            }
+        )*
+    };
+}
+
+respan_literals!(
+    syn::Lit,
+    syn::LitStr,
+    syn::LitByteStr,
+    syn::LitByte,
+    syn::LitChar,
+    syn::LitInt,
+    syn::LitFloat
+);
+
+// the token types of syn are replaced by new tokens, which are constructed by the
+// functions of the same name from the span
+macro_rules! respan_tokens {
+    ($($token:ident)*) => {
+        $(
+            impl Respan for syn::token::$token {
+                fn respan(&mut self, span: Span) {
+                    *self = syn::token::$token(span);
+                }
+            }
+        )*
+    };
+}
+
+respan_tokens! {
+    Abstract As Async Auto Await Become Box Break Const Continue Crate Default Do Dyn Else Enum
+    Extern Final Fn For If Impl In Let Loop Macro Match Mod Move Mut Override Priv Pub Ref Return
+    SelfType SelfValue Static Struct Super Trait Try Type Typeof Union Unsafe Unsized Use Virtual
+    Where While Yield
+    And AndAnd AndEq At Caret CaretEq Colon Comma Dollar Dot DotDot DotDotDot DotDotEq Eq EqEq
+    FatArrow Ge Gt LArrow Le Lt Minus MinusEq Ne Not Or OrEq OrOr PathSep Percent PercentEq Plus
+    PlusEq Pound Question RArrow Semi Shl ShlEq Shr ShrEq Slash SlashEq Star StarEq Tilde Underscore
+    Brace Bracket Paren Group
+}
+
+// the syntax tree types of syn are respanned by visiting all of their spans. The visitors of syn
+// skip the tokens, so the punctuation and delimiters are respanned by parsing the respanned tokens
+// again. This only fails for values that don't round trip through their tokens, whose punctuation
+// keeps its spans then.
+macro_rules! respan_by_visiting {
+    ($($syntax_tree:ty => $visit:ident),*) => {
+        $(
+            impl Respan for $syntax_tree {
+                fn respan(&mut self, span: Span) {
+                    syn::visit_mut::$visit(&mut SetSpan(span), self);
+                    let mut tokens = self.to_token_stream();
+                    tokens.respan(span);
+                    if let Ok(respanned) = syn::parse2(tokens) {
+                        *self = respanned;
+                    }
+                }
+            }
+        )*
+    };
+}
+
+respan_by_visiting!(
+    syn::Expr => visit_expr_mut,
+    syn::Type => visit_type_mut,
+    syn::Path => visit_path_mut
+);
+
+// a visitor that sets every span of a syntax tree to the given span. Besides the spans of syn's
+// nodes, this covers the literals and the verbatim tokens, e.g. of macros, which syn doesn't
+// visit the spans of.
+struct SetSpan(Span);
+
+// the literals of syn whose spans are set by the visitor
+macro_rules! set_span_of_literals {
+    ($($visit:ident: $literal:ty),*) => {
+        $(
+            fn $visit(&mut self, literal: &mut $literal) {
+                literal.set_span(self.0);
+            }
+        )*
+    };
+}
+
+impl syn::visit_mut::VisitMut for SetSpan {
+    fn visit_span_mut(&mut self, span: &mut Span) {
+        *span = self.0;
+    }
+
+    fn visit_token_stream_mut(&mut self, tokens: &mut TokenStream) {
+        tokens.respan(self.0);
+    }
+
+    set_span_of_literals!(
+        visit_lit_mut: syn::Lit,
+        visit_lit_str_mut: syn::LitStr,
+        visit_lit_byte_str_mut: syn::LitByteStr,
+        visit_lit_cstr_mut: syn::LitCStr,
+        visit_lit_byte_mut: syn::LitByte,
+        visit_lit_char_mut: syn::LitChar,
+        visit_lit_int_mut: syn::LitInt,
+        visit_lit_float_mut: syn::LitFloat
+    );
+}
+
+impl<T: Respan> Respan for Option<T> {
+    fn respan(&mut self, span: Span) {
+        if let Some(value) = self {
+            value.respan(span);
+        }
+    }
+}
+
+impl<T: Respan> Respan for Box<T> {
+    fn respan(&mut self, span: Span) {
+        (**self).respan(span);
+    }
+}
+
+impl<T: Respan> Respan for Vec<T> {
+    fn respan(&mut self, span: Span) {
+        for value in self.iter_mut() {
+            value.respan(span);
+        }
+    }
+}
+
+impl<T: Respan, P: Respan> Respan for Punctuated<T, P> {
+    fn respan(&mut self, span: Span) {
+        for pair in self.pairs_mut() {
+            match pair {
+                Pair::Punctuated(value, punct) => {
+                    value.respan(span);
+                    punct.respan(span);
+                }
+                Pair::End(value) => value.respan(span),
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+// helpers for the code that is generated for the syntax templates of variants, i.e.
+// `#[parse(syntax = "...")]`. The literal tokens of a template are matched and emitted
+// by their textual representation, since not every token can be named as a type like `Token![=]`.
+use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+use syn::parse::ParseStream;
+
+// parse the identifier (or keyword) with the given name from the input and drop it
+pub fn parse_ident(input: ParseStream, expected: &str) -> syn::Result<()> {
+    input.step(|cursor| match cursor.ident() {
+        Some((ident, rest)) if ident == expected => Ok(((), rest)),
+        _ => Err(cursor.error(format!("expected `{}`", expected))),
+    })
+}
+
+// parse the punctuation character from the input and drop it. If `joint` is true, the
+// character must be immediately followed by another punctuation character, like `=` in `=>`.
+pub fn parse_punct(input: ParseStream, expected: char, joint: bool) -> syn::Result<()> {
+    input.step(|cursor| match cursor.punct() {
+        Some((punct, rest))
+            if punct.as_char() == expected && (!joint || punct.spacing() == Spacing::Joint) =>
+        {
+            Ok(((), rest))
+        }
+        _ => Err(cursor.error(format!("expected `{}`", expected))),
+    })
+}
+
+// parse the literal with the given textual representation from the input and drop it
+pub fn parse_literal(input: ParseStream, expected: &str) -> syn::Result<()> {
+    input.step(|cursor| match cursor.literal() {
+        Some((literal, rest)) if literal.to_string() == expected => Ok(((), rest)),
+        _ => Err(cursor.error(format!("expected `{}`", expected))),
+    })
+}
+
+// emit the identifier (or keyword) with the given name. Raw identifiers like `r#type` are supported.
+pub fn emit_ident(tokens: &mut TokenStream, name: &str) {
+    let ident = match name.strip_prefix("r#") {
+        Some(name) => Ident::new_raw(name, Span::call_site()),
+        None => Ident::new(name, Span::call_site()),
+    };
+    tokens.extend(std::iter::once(TokenTree::Ident(ident)));
+}
+
+// emit the punctuation character with the given spacing
+pub fn emit_punct(tokens: &mut TokenStream, character: char, joint: bool) {
+    let spacing = if joint {
+        Spacing::Joint
+    } else {
+        Spacing::Alone
+    };
+    tokens.extend(std::iter::once(TokenTree::Punct(Punct::new(
+        character, spacing,
+    ))));
+}
+
+// emit the literal with the given textual representation
+pub fn emit_literal(tokens: &mut TokenStream, literal: &str) {
+    let literal: TokenStream = literal
+        .parse()
+        .expect("literals of syntax templates are checked at compile time");
+    tokens.extend(literal);
+}
+
+// emit a group with the given delimiter, whose content is emitted by the given closure
+pub fn emit_group(
+    tokens: &mut TokenStream,
+    delimiter: Delimiter,
+    content: impl FnOnce(&mut TokenStream),
+) {
+    let mut content_tokens = TokenStream::new();
+    content(&mut content_tokens);
+    tokens.extend(std::iter::once(TokenTree::Group(Group::new(
+        delimiter,
+        content_tokens,
+    ))));
+}
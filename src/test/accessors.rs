@@ -0,0 +1,77 @@
+use assert2::check;
+use assert2::let_assert;
+use proc_macro2::{Span, TokenStream};
+use syn::{Expr, Ident, LitInt, Token};
+
+use crate::Parse;
+
+#[derive(Parse)]
+#[parse(accessors, from)]
+#[allow(dead_code)]
+enum Number {
+    #[parse(rename = "nan")]
+    Nan,
+    Identifier(Ident),
+    Sum(LitInt, Token![+], Expr),
+    Literal {
+        literal: LitInt,
+    },
+}
+
+// only `Value` is converted from its field: the other fields are not parsed, so that the
+// two `TokenStream` fields do not conflict
+#[derive(Parse)]
+#[parse(from)]
+#[allow(dead_code)]
+enum Command {
+    Value(LitInt),
+    #[parse(rename = "pass")]
+    Pass(#[parse(span)] Span),
+    #[parse(rename = "stop")]
+    Stop(#[parse(tokens)] TokenStream),
+    #[parse(fallback)]
+    Verbatim(TokenStream),
+}
+
+#[test]
+fn variants_are_identified() {
+    let number = syn::parse_str::<Number>("x").unwrap();
+    check!(number.is_identifier());
+    check!(!number.is_literal());
+    let number = syn::parse_str::<Number>("nan").unwrap();
+    check!(number.is_nan());
+}
+
+#[test]
+fn fields_are_accessed_by_reference() {
+    let number = syn::parse_str::<Number>("x").unwrap();
+    let_assert!(Some(ident) = number.as_identifier());
+    check!(ident == "x");
+    check!(number.as_literal().is_none());
+
+    let number = syn::parse_str::<Number>("1 + x").unwrap();
+    let_assert!(Some((literal, _, _)) = number.as_sum());
+    check!(literal.base10_digits() == "1");
+}
+
+#[test]
+fn fields_are_taken_by_conversion() {
+    let number = syn::parse_str::<Number>("7").unwrap();
+    let_assert!(Err(number) = number.into_identifier());
+    let_assert!(Ok(literal) = number.into_literal());
+    check!(literal.base10_digits() == "7");
+}
+
+#[test]
+fn variants_with_a_single_field_are_converted_from_the_field() {
+    let ident: Ident = syn::parse_str("x").unwrap();
+    let_assert!(Number::Identifier(_) = Number::from(ident));
+    let literal: LitInt = syn::parse_str("3").unwrap();
+    let_assert!(Number::Literal { .. } = literal.into());
+}
+
+#[test]
+fn fields_that_are_not_parsed_are_not_converted_from() {
+    let literal: LitInt = syn::parse_str("3").unwrap();
+    let_assert!(Command::Value(_) = Command::from(literal));
+}
@@ -0,0 +1,67 @@
+use assert2::check;
+use assert2::let_assert;
+use quote::ToTokens;
+
+use crate::Parse;
+
+#[derive(Parse, crate::ToTokens, Debug)]
+#[parse(rename_all = "lowercase")]
+enum LengthUnit {
+    #[parse(alias = "m", alias = "metre")]
+    Meters,
+    #[parse(alias = "ft")]
+    Feet(#[parse(spelling)] proc_macro2::TokenStream),
+    #[parse(canonical, alias = "in")]
+    Inches(#[parse(spelling)] proc_macro2::TokenStream),
+    #[parse(rename = "fast", alias = "fast-forward")]
+    Fast,
+}
+
+#[derive(Parse, crate::ToTokens, Debug)]
+enum Arrow {
+    #[parse(syntax = "=>", alias = "->")]
+    Right(#[parse(spelling)] proc_macro2::TokenStream),
+    #[parse(syntax = "<=", alias = "<-")]
+    Left,
+}
+
+#[test]
+fn aliases_are_accepted_as_spellings() {
+    for input in ["meters", "m", "metre"] {
+        let_assert!(Ok(LengthUnit::Meters) = syn::parse_str::<LengthUnit>(input));
+    }
+    let_assert!(Ok(LengthUnit::Feet(_)) = syn::parse_str::<LengthUnit>("ft"));
+    let_assert!(Ok(LengthUnit::Inches(_)) = syn::parse_str::<LengthUnit>("in"));
+    let_assert!(Ok(LengthUnit::Fast) = syn::parse_str::<LengthUnit>("fast"));
+    let_assert!(Ok(LengthUnit::Fast) = syn::parse_str::<LengthUnit>("fast-forward"));
+
+    let_assert!(Ok(Arrow::Right(_)) = syn::parse_str::<Arrow>("->"));
+    let_assert!(Ok(Arrow::Left) = syn::parse_str::<Arrow>("<-"));
+    check!(syn::parse_str::<Arrow>("<>").is_err());
+}
+
+#[test]
+fn spelling_field_keeps_the_original_spelling() {
+    let_assert!(Ok(LengthUnit::Feet(spelling)) = syn::parse_str::<LengthUnit>("ft"));
+    check!(spelling.to_string() == "ft");
+    let_assert!(Ok(Arrow::Right(spelling)) = syn::parse_str::<Arrow>("->"));
+    check!(spelling.to_string() == "->");
+}
+
+#[test]
+fn original_or_canonical_spelling_is_emitted() {
+    let unit = syn::parse_str::<LengthUnit>("ft").unwrap();
+    check!(unit.to_token_stream().to_string() == "ft");
+    let unit = syn::parse_str::<LengthUnit>("in").unwrap();
+    check!(unit.to_token_stream().to_string() == "inches");
+    let unit = syn::parse_str::<LengthUnit>("metre").unwrap();
+    check!(unit.to_token_stream().to_string() == "meters");
+    let arrow = syn::parse_str::<Arrow>("<-").unwrap();
+    check!(arrow.to_token_stream().to_string() == "<=");
+}
+
+#[test]
+fn aliases_are_suggested_for_misspelled_keywords() {
+    let_assert!(Err(error) = syn::parse_str::<LengthUnit>("metr"));
+    check!(error.to_string() == "unknown length unit `metr`, did you mean `metre`?");
+}
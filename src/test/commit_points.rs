@@ -0,0 +1,70 @@
+use assert2::check;
+use assert2::let_assert;
+use proc_macro2::TokenStream;
+use syn::{Ident, LitInt, Token};
+
+use crate::Parse;
+
+#[derive(Parse)]
+#[allow(dead_code)]
+enum Statement {
+    Let {
+        #[parse(commit)]
+        _let: Token![let],
+        name: Ident,
+        _eq: Token![=],
+        value: LitInt,
+    },
+    Static(#[parse(commit)] Token![static], Ident),
+    #[parse(fallback)]
+    Verbatim(TokenStream),
+}
+
+#[derive(Parse)]
+#[allow(dead_code)]
+enum Block {
+    #[parse(syntax = "begin $($0 $1),* end")]
+    Statements(#[parse(commit)] Vec<Token![let]>, Vec<Ident>),
+    Fallback(Ident, Token![let]),
+}
+
+#[test]
+fn committed_variants_are_parsed() {
+    let variant = syn::parse_str::<Statement>("let abides = 42").unwrap();
+    let_assert!(Statement::Let { name, value, .. } = variant);
+    check!(name == "abides");
+    check!(value == syn::parse_str::<LitInt>("42").unwrap());
+
+    let variant = syn::parse_str::<Statement>("static dude").unwrap();
+    let_assert!(Statement::Static(_, name) = variant);
+    check!(name == "dude");
+}
+
+#[test]
+fn errors_before_the_commit_point_try_the_next_variant() {
+    let variant = syn::parse_str::<Statement>("const abides = 42").unwrap();
+    let_assert!(Statement::Verbatim(tokens) = variant);
+    check!(tokens.to_string() == "const abides = 42");
+}
+
+#[test]
+fn errors_after_the_commit_point_are_returned() {
+    let_assert!(Err(error) = syn::parse_str::<Statement>("let abides = walter"));
+    check!(error.to_string() == "expected integer literal");
+
+    let_assert!(Err(error) = syn::parse_str::<Statement>("static 42"));
+    check!(error.to_string() == "expected identifier");
+}
+
+#[test]
+fn failed_repetitions_do_not_commit() {
+    let_assert!(Ok(Block::Fallback(..)) = syn::parse_str::<Block>("begin let"));
+    let_assert!(
+        Ok(Block::Statements(_, names)) = syn::parse_str::<Block>("begin let x, let y end")
+    );
+    check!(names.len() == 2);
+
+    // the accepted repetition commits the variant
+    let_assert!(Err(error) = syn::parse_str::<Block>("begin let x let"));
+    check!(error.to_string() == "expected `end`");
+}
@@ -0,0 +1,99 @@
+use assert2::check;
+use assert2::let_assert;
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use syn::{Expr, Ident, Token};
+
+use crate::Parse;
+
+mod keywords {
+    syn::custom_keyword!(lebowski);
+}
+
+#[derive(Parse, crate::ToTokens)]
+#[parse(constructors)]
+#[allow(dead_code)]
+enum EnumWithMixedVariants {
+    TwoExpressionsSeparatedByKeyword {
+        first: Expr,
+        #[parse(default)]
+        _the_dude: keywords::lebowski,
+        second: Expr,
+    },
+    IdentifierPlusPlus(Ident, syn::token::Plus, Token![+]),
+    #[parse(parenthesized, sep = Token![,])]
+    Pair(
+        Ident,
+        Ident,
+        #[parse(separators)] Vec<Token![,]>,
+        #[parse(span)] Span,
+    ),
+    Nothing,
+}
+
+#[derive(Parse, crate::ToTokens)]
+#[parse(constructors)]
+enum PathStart {
+    Crate(Token![crate]),
+    Super(Token![super]),
+    Type(Token![type]),
+}
+
+#[derive(Parse, crate::ToTokens)]
+#[parse(constructors, rename_all = "kebab-case")]
+enum Step {
+    #[parse(alias = "ff")]
+    FastForward(#[parse(spelling)] TokenStream),
+    #[parse(syntax = "=>", alias = "->")]
+    Arrow(#[parse(spelling)] TokenStream),
+}
+
+#[test]
+fn token_fields_are_default_constructed() {
+    let first = syn::parse_str::<Expr>("jeffrey()").unwrap();
+    let second = syn::parse_str::<Expr>("el.duderino").unwrap();
+    let variant = EnumWithMixedVariants::two_expressions_separated_by_keyword(first, second);
+    check!(variant.to_token_stream().to_string() == "jeffrey () lebowski el . duderino");
+
+    let ident = syn::parse_str::<Ident>("C").unwrap();
+    let variant = EnumWithMixedVariants::identifier_plus_plus(ident);
+    check!(variant.to_token_stream().to_string() == "C + +");
+}
+
+#[test]
+fn unparsed_fields_are_default_constructed() {
+    let first = syn::parse_str::<Ident>("x").unwrap();
+    let second = syn::parse_str::<Ident>("y").unwrap();
+    let variant = EnumWithMixedVariants::pair(first, second);
+    let_assert!(EnumWithMixedVariants::Pair(_, _, separators, _) = &variant);
+    check!(separators.is_empty());
+    check!(variant.to_token_stream().to_string() == "(x , y)");
+}
+
+#[test]
+fn constructed_variants_can_be_parsed_back() {
+    let variant = EnumWithMixedVariants::nothing();
+    let_assert!(EnumWithMixedVariants::Nothing = variant);
+
+    let ident = syn::parse_str::<Ident>("C").unwrap();
+    let tokens = EnumWithMixedVariants::identifier_plus_plus(ident).to_token_stream();
+    let_assert!(
+        Ok(EnumWithMixedVariants::IdentifierPlusPlus(..)) =
+            syn::parse2::<EnumWithMixedVariants>(tokens)
+    );
+}
+
+#[test]
+fn constructors_named_after_keywords_are_escaped() {
+    check!(PathStart::crate_().to_token_stream().to_string() == "crate");
+    check!(PathStart::super_().to_token_stream().to_string() == "super");
+    check!(PathStart::r#type().to_token_stream().to_string() == "type");
+}
+
+#[test]
+fn spelling_fields_get_the_canonical_spelling() {
+    let_assert!(Step::FastForward(spelling) = Step::fast_forward());
+    check!(spelling.to_string() == "fast - forward");
+    check!(Step::arrow().to_token_stream().to_string() == "=>");
+    let_assert!(Ok(Step::Arrow(_)) = syn::parse2::<Step>(Step::arrow().to_token_stream()));
+}
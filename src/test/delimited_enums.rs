@@ -0,0 +1,53 @@
+use assert2::check;
+use assert2::let_assert;
+use quote::ToTokens;
+use syn::{Ident, LitInt, Token};
+
+use crate::Parse;
+
+#[derive(Parse, crate::ToTokens)]
+#[parse(delimited = parens)]
+enum Mode {
+    Speed(Ident),
+    Level(LitInt),
+}
+
+#[derive(Parse, crate::ToTokens)]
+#[parse(prefix = Token![#], delimited = brackets)]
+enum Attribute {
+    Assignment(Ident, Token![=], LitInt),
+    Flag(Ident),
+}
+
+#[test]
+fn variants_are_parsed_from_the_group() {
+    let mode = syn::parse_str::<Mode>("(fast)").unwrap();
+    let_assert!(Mode::Speed(speed) = mode);
+    check!(speed == "fast");
+
+    let mode = syn::parse_str::<Mode>("(3)").unwrap();
+    let_assert!(Mode::Level(_) = mode);
+
+    let attribute = syn::parse_str::<Attribute>("#[level = 3]").unwrap();
+    let_assert!(Attribute::Assignment(ident, _, _) = attribute);
+    check!(ident == "level");
+}
+
+#[test]
+fn content_of_group_must_be_used_up() {
+    check!(syn::parse_str::<Mode>("fast").is_err());
+    check!(syn::parse_str::<Mode>("[fast]").is_err());
+    check!(syn::parse_str::<Attribute>("[verbose]").is_err());
+
+    let_assert!(Err(error) = syn::parse_str::<Mode>("(fast slow)"));
+    check!(error.to_string() == "unexpected token");
+}
+
+#[test]
+fn variants_are_emitted_in_the_group() {
+    let mode = syn::parse_str::<Mode>("(fast)").unwrap();
+    check!(mode.to_token_stream().to_string() == "(fast)");
+
+    let attribute = syn::parse_str::<Attribute>("#[verbose]").unwrap();
+    check!(attribute.to_token_stream().to_string() == "# [verbose]");
+}
@@ -0,0 +1,69 @@
+use assert2::check;
+use assert2::let_assert;
+use quote::ToTokens;
+use syn::{token, Expr, Ident, Token};
+
+use crate::Parse;
+
+#[derive(Parse, crate::ToTokens)]
+enum Value {
+    #[parse(parenthesized, sep = Token![,])]
+    Tuple(Expr, Expr),
+    #[parse(bracketed)]
+    Array {
+        #[parse(delimiter)]
+        bracket: token::Bracket,
+        element: Ident,
+    },
+    #[parse(braced, syntax = "$name : $value")]
+    Block {
+        name: Ident,
+        value: Expr,
+    },
+    Plain(Ident),
+}
+
+#[test]
+fn fields_are_parsed_from_the_delimited_group() {
+    let value = syn::parse_str::<Value>("(1, x + 2)").unwrap();
+    let_assert!(Value::Tuple(first, second) = value);
+    check!(first == syn::parse_str::<Expr>("1").unwrap());
+    check!(second == syn::parse_str::<Expr>("x + 2").unwrap());
+
+    let value = syn::parse_str::<Value>("{walter: 1}").unwrap();
+    let_assert!(Value::Block { name, .. } = value);
+    check!(name == "walter");
+
+    let value = syn::parse_str::<Value>("donny").unwrap();
+    let_assert!(Value::Plain(_) = value);
+}
+
+#[test]
+fn delimiter_can_be_stored() {
+    let value = syn::parse_str::<Value>("[dude]").unwrap();
+    let_assert!(Value::Array { bracket, element } = value);
+    check!(element == "dude");
+    // the span of the delimiter is available from the stored token
+    let _span = bracket.span.join();
+}
+
+#[test]
+fn content_of_group_must_be_used_up() {
+    check!(syn::parse_str::<Value>("(1, 2, 3)").is_err());
+    check!(syn::parse_str::<Value>("[the dude]").is_err());
+    check!(syn::parse_str::<Value>("1, 2").is_err());
+    check!(syn::parse_str::<Value>("(dude)").is_err());
+}
+
+#[test]
+fn delimited_variants_are_emitted_in_groups() {
+    for input in ["(1 , x + 2)", "[dude]", "{ walter : 1 }"] {
+        let value = syn::parse_str::<Value>(input).unwrap();
+        check!(
+            value.to_token_stream().to_string()
+                == syn::parse_str::<proc_macro2::TokenStream>(input)
+                    .unwrap()
+                    .to_string()
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use assert2::check;
+use assert2::let_assert;
+use quote::ToTokens;
+
+use crate::Parse;
+
+#[derive(Parse, Debug)]
+#[parse(rename_all = "lowercase")]
+enum LengthUnit {
+    Metres,
+    #[parse(deprecated = "use `metres` instead")]
+    Meters,
+}
+
+#[derive(Parse)]
+#[allow(dead_code)]
+enum Measurement {
+    Statement(LengthUnit, syn::Token![;]),
+    List(LengthUnit, syn::Token![,]),
+}
+
+#[test]
+fn deprecated_variants_record_a_warning() {
+    // warnings are collected per thread, so we start from a clean slate
+    let _ = crate::warnings();
+
+    let_assert!(Ok(LengthUnit::Meters) = syn::parse_str::<LengthUnit>("meters"));
+    let warnings = crate::warnings();
+    check!(warnings.len() == 1);
+    check!(warnings[0].message() == "use `metres` instead");
+
+    // the warnings are taken by the collector
+    check!(crate::warnings().is_empty());
+}
+
+#[test]
+fn other_variants_record_no_warning() {
+    let _ = crate::warnings();
+    let_assert!(Ok(LengthUnit::Metres) = syn::parse_str::<LengthUnit>("metres"));
+    check!(syn::parse_str::<LengthUnit>("feet").is_err());
+    check!(crate::warnings().is_empty());
+}
+
+#[test]
+fn discarded_parse_attempts_record_no_warning() {
+    let _ = crate::warnings();
+    let_assert!(Ok(Measurement::List(..)) = syn::parse_str::<Measurement>("meters ,"));
+    check!(crate::warnings().len() == 1);
+
+    check!(syn::parse_str::<Measurement>("meters").is_err());
+    check!(crate::warnings().is_empty());
+}
+
+#[test]
+fn warnings_are_capped_until_taken() {
+    let _ = crate::warnings();
+    for _ in 0..crate::MAX_WARNINGS + 10 {
+        let_assert!(Ok(_) = syn::parse_str::<LengthUnit>("meters"));
+    }
+    check!(crate::warnings().len() == crate::MAX_WARNINGS);
+
+    let_assert!(Ok(_) = syn::parse_str::<LengthUnit>("meters"));
+    check!(crate::warnings().len() == 1);
+}
+
+#[test]
+fn warnings_are_emitted_as_deprecations() {
+    let _ = crate::warnings();
+    let_assert!(Ok(_) = syn::parse_str::<LengthUnit>("meters"));
+    let warnings = crate::warnings();
+    let tokens = warnings[0].to_token_stream().to_string();
+    check!(tokens.contains("deprecated (note = \"use `metres` instead\")"));
+}
@@ -0,0 +1,45 @@
+use assert2::check;
+use assert2::let_assert;
+use quote::ToTokens;
+use syn::{Ident, Token};
+
+use crate::Parse;
+
+#[derive(Parse, crate::ToTokens)]
+enum Args {
+    #[parse(empty)]
+    None,
+    Pair(Ident, Token![,], Ident),
+    Single(Ident),
+}
+
+#[derive(Parse, crate::ToTokens)]
+#[parse(delimited = parens)]
+enum Call {
+    #[parse(empty)]
+    NoArgs,
+    Arg(Ident),
+}
+
+#[test]
+fn empty_variant_is_parsed_from_empty_input() {
+    let_assert!(Ok(Args::None) = syn::parse_str::<Args>(""));
+    let_assert!(Ok(Args::Single(ident)) = syn::parse_str::<Args>("x"));
+    check!(ident == "x");
+    let_assert!(Ok(Args::Pair(..)) = syn::parse_str::<Args>("x, y"));
+}
+
+#[test]
+fn empty_variant_is_parsed_from_empty_group() {
+    let_assert!(Ok(Call::NoArgs) = syn::parse_str::<Call>("()"));
+    let_assert!(Ok(Call::Arg(_)) = syn::parse_str::<Call>("(x)"));
+    check!(syn::parse_str::<Call>("").is_err());
+}
+
+#[test]
+fn empty_variant_is_emitted_as_nothing() {
+    let args = syn::parse_str::<Args>("").unwrap();
+    check!(args.to_token_stream().is_empty());
+    let call = syn::parse_str::<Call>("()").unwrap();
+    check!(call.to_token_stream().to_string() == "()");
+}
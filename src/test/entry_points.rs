@@ -0,0 +1,56 @@
+use assert2::check;
+use assert2::let_assert;
+use syn::parse::{ParseStream, Parser};
+use syn::{Ident, LitInt, Token};
+
+use crate::Parse;
+
+#[derive(Parse)]
+#[parse(kind, entry_points, prefix = Token![#])]
+#[allow(dead_code)]
+enum Setting {
+    Assignment(Ident, #[parse(commit)] Token![=], LitInt),
+    Flag(Ident),
+    #[parse(ignore)]
+    Unset,
+    #[parse(rename = "default")]
+    UseDefault,
+}
+
+#[test]
+fn entry_points_parse_only_their_variant() {
+    let_assert!(Ok(Setting::Flag(ident)) = Setting::parse_flag.parse_str("#verbose"));
+    check!(ident == "verbose");
+    let_assert!(Ok(Setting::Assignment(..)) = Setting::parse_assignment.parse_str("#level = 3"));
+    let_assert!(Ok(Setting::UseDefault) = Setting::parse_use_default.parse_str("#default"));
+    check!(Setting::parse_flag.parse_str("#level = 3").is_err());
+}
+
+#[test]
+fn entry_points_return_the_error_of_their_variant() {
+    let_assert!(Err(error) = Setting::parse_assignment.parse_str("#verbose"));
+    check!(error.to_string() == "expected `=`");
+    let_assert!(Err(error) = Setting::parse_use_default.parse_str("#verbose"));
+    check!(error.to_string() == "expected `default`");
+    let_assert!(Err(error) = Setting::parse_flag.parse_str("verbose"));
+    check!(error.to_string() == "expected `#`");
+}
+
+#[test]
+fn variants_are_parsed_by_kind() {
+    let parse_flag = |input: ParseStream| Setting::parse_variant(SettingKind::Flag, input);
+    let_assert!(Ok(Setting::Flag(_)) = parse_flag.parse_str("#verbose"));
+
+    let parse_unset = |input: ParseStream| Setting::parse_variant(SettingKind::Unset, input);
+    let_assert!(Err(error) = parse_unset.parse_str("#verbose"));
+    check!(error.to_string() == "parse error: variant Unset of Setting is ignored");
+
+    for (kind, source) in [
+        (SettingKind::Assignment, "#level = 3"),
+        (SettingKind::UseDefault, "#default"),
+    ] {
+        let parse = |input: ParseStream| Setting::parse_variant(kind, input);
+        let_assert!(Ok(setting) = parse.parse_str(source));
+        check!(setting.kind() == kind);
+    }
+}
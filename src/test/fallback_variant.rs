@@ -0,0 +1,72 @@
+use assert2::check;
+use assert2::let_assert;
+use proc_macro2::TokenStream;
+use syn::parse::ParseStream;
+use syn::{Ident, LitInt, Token};
+
+use crate::Parse;
+
+#[derive(Parse)]
+enum EnumWithFallback {
+    #[parse(fallback)]
+    Verbatim(TokenStream),
+    #[parse(priority = -1)]
+    Integer(LitInt),
+    Identifier(Ident),
+}
+
+#[derive(Parse)]
+enum EnumWithTerminatedFallback {
+    Assignment {
+        name: Ident,
+        _eq: Token![=],
+        value: LitInt,
+    },
+    #[parse(fallback, terminator = Token![;])]
+    Verbatim { tokens: TokenStream },
+}
+
+#[test]
+fn fallback_is_only_used_if_no_other_variant_can_be_parsed() {
+    let variant = syn::parse_str::<EnumWithFallback>("1337").unwrap();
+    let_assert!(EnumWithFallback::Integer(int) = variant);
+    check!(int == syn::parse_str::<LitInt>("1337").unwrap());
+
+    let variant = syn::parse_str::<EnumWithFallback>("dude").unwrap();
+    let_assert!(EnumWithFallback::Identifier(ident) = variant);
+    check!(ident == "dude");
+}
+
+#[test]
+fn fallback_captures_all_remaining_tokens() {
+    let variant = syn::parse_str::<EnumWithFallback>("+ the dude { abides }").unwrap();
+    let_assert!(EnumWithFallback::Verbatim(tokens) = variant);
+    check!(tokens.to_string() == "+ the dude { abides }");
+}
+
+#[test]
+fn fallback_stops_at_terminator() {
+    let (variant, _semi) = syn::parse::Parser::parse_str(
+        |input: ParseStream| {
+            Ok((
+                input.parse::<EnumWithTerminatedFallback>()?,
+                input.parse::<Token![;]>()?,
+            ))
+        },
+        "walter + sobchak;",
+    )
+    .unwrap();
+    let_assert!(EnumWithTerminatedFallback::Verbatim { tokens } = variant);
+    check!(tokens.to_string() == "walter + sobchak");
+
+    let variant = syn::parse_str::<EnumWithTerminatedFallback>("abides = 1").unwrap();
+    let_assert!(EnumWithTerminatedFallback::Assignment { name, value, .. } = variant);
+    check!(name == "abides");
+    check!(value == syn::parse_str::<LitInt>("1").unwrap());
+}
+
+#[test]
+fn fallback_needs_at_least_one_token() {
+    check!(syn::parse_str::<EnumWithFallback>("").is_err());
+    check!(syn::parse_str::<EnumWithTerminatedFallback>(";").is_err());
+}
@@ -0,0 +1,43 @@
+use assert2::check;
+use assert2::let_assert;
+use syn::{Expr, Ident, Token};
+
+use crate::Parse;
+
+mod kw {
+    syn::custom_keyword!(meters);
+}
+
+#[derive(Parse)]
+#[parse(from_str)]
+#[allow(dead_code)]
+enum Length {
+    InMeters(Expr, kw::meters),
+    Assignment(Ident, Token![=], Expr),
+}
+
+#[test]
+fn strings_are_parsed_as_the_enumeration() {
+    let_assert!(Ok(Length::InMeters(..)) = "16 + 2 meters".parse::<Length>());
+    let_assert!(Ok(Length::Assignment(ident, ..)) = "x = 2".parse::<Length>());
+    check!(ident == "x");
+}
+
+#[test]
+#[cfg(feature = "span-locations")]
+fn errors_contain_the_location() {
+    let_assert!(Err(error) = "x = 1\n  2".parse::<Length>());
+    check!(error.to_string() == "line 2, column 3: unexpected token");
+
+    let_assert!(Err(error) = "16 feet".parse::<Length>());
+    check!(error
+        .to_string()
+        .starts_with("line 1, column 1: parse error"));
+}
+
+#[test]
+#[cfg(not(feature = "span-locations"))]
+fn errors_are_returned_as_they_are() {
+    let_assert!(Err(error) = "x = 1\n  2".parse::<Length>());
+    check!(error.to_string() == "unexpected token");
+}
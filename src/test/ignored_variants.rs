@@ -0,0 +1,45 @@
+use assert2::check;
+use assert2::let_assert;
+use syn::{Ident, LitInt};
+
+use crate::Parse;
+
+// does not implement syn::parse::Parse
+#[derive(Debug, PartialEq)]
+struct TypeId(u64);
+
+#[derive(Parse)]
+#[allow(dead_code)]
+enum EnumWithIgnoredVariants {
+    #[parse(ignore)]
+    Resolved(TypeId),
+    Named(Ident),
+    #[parse(ignore)]
+    Unknown,
+    #[parse(ignore)]
+    IgnoredInteger {
+        int: LitInt,
+    },
+    Integer(LitInt),
+}
+
+#[test]
+fn variants_that_are_not_ignored_are_parsed() {
+    let variant = syn::parse_str::<EnumWithIgnoredVariants>("donny").unwrap();
+    let_assert!(EnumWithIgnoredVariants::Named(ident) = variant);
+    check!(ident == syn::parse_str::<Ident>("donny").unwrap());
+}
+
+#[test]
+fn ignored_variants_are_never_parsed() {
+    // the ignored struct like variant comes first, but must not be returned
+    let variant = syn::parse_str::<EnumWithIgnoredVariants>("42").unwrap();
+    let_assert!(EnumWithIgnoredVariants::Integer(int) = variant);
+    check!(int == syn::parse_str::<LitInt>("42").unwrap());
+}
+
+#[test]
+fn parsing_gives_error_if_none_of_the_variants_can_be_parsed() {
+    let variant = syn::parse_str::<EnumWithIgnoredVariants>("+ - *");
+    check!(variant.is_err());
+}
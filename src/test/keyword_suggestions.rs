@@ -0,0 +1,53 @@
+use assert2::check;
+use assert2::let_assert;
+
+use crate::Parse;
+
+#[derive(Parse, Debug)]
+#[parse(rename_all = "lowercase")]
+enum LengthUnit {
+    Meters,
+    Feet,
+    Inches,
+}
+
+#[derive(Parse, Debug)]
+#[allow(dead_code)]
+enum Value {
+    Number(syn::LitInt),
+    Nothing,
+}
+
+#[test]
+fn misspelled_keywords_suggest_the_closest_keyword() {
+    let_assert!(Err(error) = syn::parse_str::<LengthUnit>("meterz"));
+    check!(error.to_string() == "unknown length unit `meterz`, did you mean `meters`?");
+
+    let_assert!(Err(error) = syn::parse_str::<LengthUnit>("Meters"));
+    check!(error.to_string() == "unknown length unit `Meters`, did you mean `meters`?");
+
+    let_assert!(Err(error) = syn::parse_str::<LengthUnit>("Fet"));
+    check!(error.to_string() == "unknown length unit `Fet`, did you mean `feet`?");
+
+    let_assert!(Err(error) = syn::parse_str::<Value>("Nothin"));
+    check!(error.to_string() == "unknown value `Nothin`, did you mean `Nothing`?");
+}
+
+#[test]
+fn suggestion_is_spanned_to_the_misspelled_keyword() {
+    let tokens: proc_macro2::TokenStream = "inchs".parse().unwrap();
+    let ident_span = tokens.clone().into_iter().next().unwrap().span();
+    let_assert!(Err(error) = syn::parse2::<LengthUnit>(tokens));
+    check!(format!("{:?}", error.span()) == format!("{:?}", ident_span));
+}
+
+#[test]
+fn unrelated_input_gives_generic_error() {
+    for input in ["kilograms", "1", "+"] {
+        let_assert!(Err(error) = syn::parse_str::<LengthUnit>(input));
+        check!(
+            error.to_string()
+                == "parse error: tokens cannot be parsed as any variant of LengthUnit"
+        );
+    }
+}
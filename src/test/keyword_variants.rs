@@ -0,0 +1,81 @@
+use assert2::check;
+use assert2::let_assert;
+use quote::ToTokens;
+
+use crate::Parse;
+
+#[derive(Parse, crate::ToTokens, Debug, PartialEq)]
+enum Speed {
+    Fast,
+    Slow,
+    #[parse(rename = "type")]
+    Type,
+}
+
+#[derive(Parse, crate::ToTokens, Debug, PartialEq)]
+#[parse(rename_all = "kebab-case")]
+enum Direction {
+    FastForward,
+    #[parse(rename = "rew")]
+    Rewind,
+    #[parse(case_insensitive)]
+    Stop,
+}
+
+#[derive(Parse, crate::ToTokens, Debug, PartialEq)]
+#[parse(rename_all = "SCREAMING_SNAKE_CASE", case_insensitive)]
+enum Level {
+    Debug,
+    WarnOnce,
+}
+
+#[derive(Parse, crate::ToTokens, Debug, PartialEq)]
+#[parse(rename_all = "snake_case")]
+enum Protocol {
+    HTTPServer,
+    Ipv4Address,
+}
+
+#[test]
+fn unit_variants_are_parsed_from_keywords() {
+    check!(syn::parse_str::<Speed>("Fast").unwrap() == Speed::Fast);
+    check!(syn::parse_str::<Speed>("Slow").unwrap() == Speed::Slow);
+    check!(syn::parse_str::<Speed>("type").unwrap() == Speed::Type);
+    check!(syn::parse_str::<Speed>("fast").is_err());
+    check!(syn::parse_str::<Speed>("Type").is_err());
+}
+
+#[test]
+fn keywords_can_be_renamed() {
+    check!(syn::parse_str::<Direction>("fast-forward").unwrap() == Direction::FastForward);
+    check!(syn::parse_str::<Direction>("rew").unwrap() == Direction::Rewind);
+    check!(syn::parse_str::<Direction>("rewind").is_err());
+    check!(syn::parse_str::<Direction>("fast_forward").is_err());
+    check!(syn::parse_str::<Direction>("fast - forward").unwrap() == Direction::FastForward);
+}
+
+#[test]
+fn acronyms_are_a_single_word_in_snake_case() {
+    check!(syn::parse_str::<Protocol>("http_server").unwrap() == Protocol::HTTPServer);
+    check!(syn::parse_str::<Protocol>("ipv4_address").unwrap() == Protocol::Ipv4Address);
+    check!(syn::parse_str::<Protocol>("h_t_t_p_server").is_err());
+}
+
+#[test]
+fn keywords_can_be_case_insensitive() {
+    check!(syn::parse_str::<Direction>("STOP").unwrap() == Direction::Stop);
+    check!(syn::parse_str::<Level>("DEBUG").unwrap() == Level::Debug);
+    check!(syn::parse_str::<Level>("debug").unwrap() == Level::Debug);
+    check!(syn::parse_str::<Level>("warn_once").unwrap() == Level::WarnOnce);
+    check!(syn::parse_str::<Level>("WarnOnce").is_err());
+}
+
+#[test]
+fn keywords_are_emitted() {
+    check!(Direction::FastForward.to_token_stream().to_string() == "fast - forward");
+    check!(Speed::Type.to_token_stream().to_string() == "type");
+    check!(Level::WarnOnce.to_token_stream().to_string() == "WARN_ONCE");
+
+    let_assert!(Ok(direction) = syn::parse2::<Direction>(Direction::FastForward.to_token_stream()));
+    check!(direction == Direction::FastForward);
+}
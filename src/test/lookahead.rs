@@ -0,0 +1,64 @@
+use assert2::check;
+use assert2::let_assert;
+use syn::parse::ParseStream;
+use syn::punctuated::Punctuated;
+use syn::{Expr, Ident, LitInt, Token};
+
+use crate::Parse;
+
+#[derive(Parse)]
+#[allow(dead_code)]
+enum Argument {
+    #[parse(not_followed_by = Token![=], not_followed_by = Token![:])]
+    Flag(Ident),
+    Assignment(Ident, Token![=], LitInt),
+    Typed(Ident, Token![:], Ident),
+}
+
+#[derive(Parse)]
+#[allow(dead_code)]
+enum Statement {
+    #[parse(followed_by = Token![;])]
+    Terminated(Expr),
+    Block(syn::Block),
+}
+
+fn parse_arguments(input: &str) -> syn::Result<Vec<Argument>> {
+    let arguments =
+        syn::parse::Parser::parse_str(Punctuated::<Argument, Token![,]>::parse_terminated, input)?;
+    Ok(arguments.into_iter().collect())
+}
+
+#[test]
+fn not_followed_by_rejects_variant_if_token_follows() {
+    let arguments = parse_arguments("dude, rug = 1, abides: bool").unwrap();
+    let_assert!(
+        [
+            Argument::Flag(flag),
+            Argument::Assignment(name, _, value),
+            Argument::Typed(typed, _, ty)
+        ] = &arguments[..]
+    );
+    check!(flag == "dude");
+    check!(name == "rug");
+    check!(value == &syn::parse_str::<LitInt>("1").unwrap());
+    check!(typed == "abides");
+    check!(ty == "bool");
+}
+
+#[test]
+fn followed_by_requires_token_without_consuming_it() {
+    let (statement, _semi) = syn::parse::Parser::parse_str(
+        |input: ParseStream| Ok((input.parse::<Statement>()?, input.parse::<Token![;]>()?)),
+        "the + dude;",
+    )
+    .unwrap();
+    let_assert!(Statement::Terminated(expression) = statement);
+    check!(expression == syn::parse_str::<Expr>("the + dude").unwrap());
+}
+
+#[test]
+fn followed_by_fails_if_token_is_missing() {
+    check!(syn::parse_str::<Statement>("the + dude").is_err());
+    let_assert!(Ok(Statement::Block(_)) = syn::parse_str::<Statement>("{ the + dude }"));
+}
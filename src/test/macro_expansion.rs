@@ -2,3 +2,8 @@
 fn approval_test_macro_expansion() {
     macrotest::expand("macro_expansion_resources/mixed_enum.rs");
 }
+
+#[test]
+fn approval_test_macro_expansion_with_priorities() {
+    macrotest::expand("macro_expansion_resources/priority_enum.rs");
+}
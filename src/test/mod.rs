@@ -1,4 +1,31 @@
+mod accessors;
+mod aliases;
+mod commit_points;
+mod constructors;
+mod delimited_enums;
+mod delimited_variants;
+mod deprecated_variants;
+mod empty_variants;
+mod entry_points;
+mod fallback_variant;
+mod from_str;
+mod ignored_variants;
+mod keyword_suggestions;
+mod keyword_variants;
+mod lookahead;
 mod macro_expansion;
 mod mixed_variants;
+mod prefix_and_suffix;
+mod respan;
+mod separators;
+#[cfg(feature = "serde")]
+mod serde;
+mod span_fields;
 mod struct_like_variants;
+mod syntax_templates;
+mod to_tokens;
+mod token_fields;
 mod tuple_like_variants;
+mod variant_kinds;
+mod variant_priority;
+mod visitors;
@@ -0,0 +1,41 @@
+use assert2::check;
+use assert2::let_assert;
+use quote::ToTokens;
+use syn::{Expr, Ident, Token};
+
+use crate::Parse;
+
+#[derive(Parse, crate::ToTokens)]
+#[parse(prefix = Token![#], suffix = Token![;])]
+enum Directive {
+    Assignment(Ident, Token![=], Expr),
+    Name(Ident),
+}
+
+#[test]
+fn prefix_and_suffix_are_parsed_around_the_variant() {
+    let directive = syn::parse_str::<Directive>("# x = 1 + 2;").unwrap();
+    let_assert!(Directive::Assignment(ident, _, expr) = directive);
+    check!(ident == "x");
+    check!(expr == syn::parse_str::<Expr>("1 + 2").unwrap());
+
+    let directive = syn::parse_str::<Directive>("#dude;").unwrap();
+    let_assert!(Directive::Name(ident) = directive);
+    check!(ident == "dude");
+}
+
+#[test]
+fn prefix_and_suffix_are_required() {
+    check!(syn::parse_str::<Directive>("x = 1;").is_err());
+    check!(syn::parse_str::<Directive>("#dude").is_err());
+
+    // the error for the missing suffix points at the offending token
+    let_assert!(Err(error) = syn::parse_str::<Directive>("#the dude;"));
+    check!(error.to_string() == "expected `;`");
+}
+
+#[test]
+fn prefix_and_suffix_are_emitted() {
+    let directive = syn::parse_str::<Directive>("# x = 1;").unwrap();
+    check!(directive.to_token_stream().to_string() == "# x = 1 ;");
+}
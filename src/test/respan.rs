@@ -0,0 +1,78 @@
+use assert2::check;
+use assert2::let_assert;
+use proc_macro2::{LineColumn, Span, TokenStream};
+use quote::ToTokens;
+use syn::{Expr, Ident, LitInt, Token};
+
+use crate::{Parse, Respan};
+
+#[derive(Parse, crate::ToTokens, crate::Respan)]
+enum Statement {
+    Assignment(Ident, Token![=], Expr),
+    #[parse(rename = "pass")]
+    Pass(#[parse(span)] Span),
+    #[parse(ignore)]
+    #[allow(dead_code)]
+    Ignored(std::rc::Rc<str>),
+}
+
+// helper function to give the start locations of all tokens as (line, column) pairs
+fn starts(tokens: TokenStream) -> Vec<(usize, usize)> {
+    tokens
+        .into_iter()
+        .map(|token_tree| {
+            let LineColumn { line, column } = token_tree.span().start();
+            (line, column)
+        })
+        .collect()
+}
+
+fn span_at_start_of(source: &str) -> Span {
+    syn::parse_str::<Ident>(source).unwrap().span()
+}
+
+#[test]
+fn all_fields_of_the_variant_are_respanned() {
+    let mut statement = syn::parse_str::<Statement>("x = 1\n + y").unwrap();
+    check!(starts(statement.to_token_stream()) == [(1, 0), (1, 2), (1, 4), (2, 1), (2, 3)]);
+    statement.respan(span_at_start_of("  z"));
+    check!(starts(statement.to_token_stream()) == [(1, 2); 5]);
+    check!(statement.to_token_stream().to_string() == "x = 1 + y");
+}
+
+#[test]
+fn span_fields_are_respanned() {
+    let_assert!(Ok(mut statement @ Statement::Pass(_)) = syn::parse_str("\n pass"));
+    statement.respan(span_at_start_of("   z"));
+    let_assert!(Statement::Pass(span) = statement);
+    check!(span.start() == LineColumn { line: 1, column: 3 });
+}
+
+#[test]
+fn tokens_and_containers_are_respanned() {
+    let mut literals = vec![Some(syn::parse_str::<LitInt>("1").unwrap()), None];
+    literals.respan(span_at_start_of(" z"));
+    let_assert!(Some(literal) = &literals[0]);
+    check!(literal.span().start() == LineColumn { line: 1, column: 1 });
+
+    let mut tokens: TokenStream = syn::parse_str("f(x, [y])").unwrap();
+    tokens.respan(span_at_start_of("\n z"));
+    check!(starts(tokens.clone()) == [(2, 1), (2, 1)]);
+    let_assert!(Some(proc_macro2::TokenTree::Group(group)) = tokens.into_iter().nth(1));
+    check!(starts(group.stream()) == [(2, 1); 3]);
+}
+
+#[test]
+fn verbatim_tokens_of_syntax_trees_are_respanned() {
+    let mut expr = syn::parse_str::<Expr>("f(\"x\") + vec![1,\n 2]").unwrap();
+    expr.respan(span_at_start_of("  z"));
+    check!(starts(expr.to_token_stream()) == [(1, 2); 6]);
+    let_assert!(Expr::Binary(binary) = &expr);
+    let_assert!(Expr::Macro(mac) = &*binary.right);
+    check!(starts(mac.mac.tokens.clone()) == [(1, 2); 3]);
+
+    // verbatim tokens are respanned even if they don't parse back to the value
+    let mut expr = Expr::Verbatim(quote::quote! {;});
+    expr.respan(span_at_start_of("  z"));
+    check!(starts(expr.to_token_stream()) == [(1, 2)]);
+}
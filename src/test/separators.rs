@@ -0,0 +1,61 @@
+use assert2::check;
+use assert2::let_assert;
+use quote::ToTokens;
+use syn::{Expr, Ident, LitInt, Token};
+
+use crate::Parse;
+
+#[derive(Parse, crate::ToTokens)]
+enum EnumWithSeparators {
+    #[parse(sep = Token![,])]
+    Triple { a: LitInt, b: LitInt, c: LitInt },
+    #[parse(sep = Token![;])]
+    Pair(Expr, #[parse(separators)] Vec<Token![;]>, Expr),
+    #[parse(sep = Token![,])]
+    Single(Ident),
+}
+
+#[test]
+fn separators_are_parsed_between_fields() {
+    let variant = syn::parse_str::<EnumWithSeparators>("1, 2, 3").unwrap();
+    let_assert!(EnumWithSeparators::Triple { a, b, c } = variant);
+    check!(a == syn::parse_str::<LitInt>("1").unwrap());
+    check!(b == syn::parse_str::<LitInt>("2").unwrap());
+    check!(c == syn::parse_str::<LitInt>("3").unwrap());
+
+    let variant = syn::parse_str::<EnumWithSeparators>("walter").unwrap();
+    let_assert!(EnumWithSeparators::Single(ident) = variant);
+    check!(ident == "walter");
+}
+
+#[test]
+fn separators_are_required() {
+    check!(syn::parse_str::<EnumWithSeparators>("1 2, 3").is_err());
+    check!(syn::parse_str::<EnumWithSeparators>("1, 2 3").is_err());
+    check!(syn::parse_str::<EnumWithSeparators>("1, 2, 3,").is_err());
+}
+
+#[test]
+fn separators_can_be_kept() {
+    let variant = syn::parse_str::<EnumWithSeparators>("the + dude; abides").unwrap();
+    let_assert!(EnumWithSeparators::Pair(first, separators, second) = variant);
+    check!(first == syn::parse_str::<Expr>("the + dude").unwrap());
+    check!(separators.len() == 1);
+    check!(second == syn::parse_str::<Expr>("abides").unwrap());
+}
+
+#[test]
+fn separators_are_emitted_as_tokens() {
+    for input in ["1 , 2 , 3", "the + dude ; abides", "walter"] {
+        let variant = syn::parse_str::<EnumWithSeparators>(input).unwrap();
+        check!(variant.to_token_stream().to_string() == input);
+    }
+
+    // missing separators are filled in with default separators
+    let variant = EnumWithSeparators::Pair(
+        syn::parse_str("donny").unwrap(),
+        Vec::new(),
+        syn::parse_str("walter").unwrap(),
+    );
+    check!(variant.to_token_stream().to_string() == "donny ; walter");
+}
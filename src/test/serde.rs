@@ -0,0 +1,33 @@
+use assert2::check;
+use assert2::let_assert;
+use syn::{Ident, LitInt, Token};
+
+use crate::Parse;
+
+#[derive(Parse, crate::ToTokens)]
+#[parse(serde)]
+enum Setting {
+    Assignment(Ident, Token![=], LitInt),
+    Flag(Ident),
+}
+
+#[test]
+fn strings_are_deserialized_by_parsing() {
+    let_assert!(Ok(settings) = serde_json::from_str::<Vec<Setting>>(r#"["level = 3", "verbose"]"#));
+    let_assert!([Setting::Assignment(..), Setting::Flag(ident)] = &settings[..]);
+    check!(ident == "verbose");
+}
+
+#[test]
+fn parse_errors_are_deserialization_errors() {
+    let_assert!(Err(error) = serde_json::from_str::<Setting>(r#""level = 3 4""#));
+    check!(error.to_string().contains("unexpected token"));
+    check!(serde_json::from_str::<Setting>("3").is_err());
+}
+
+#[test]
+fn variants_are_serialized_as_their_tokens() {
+    let setting = syn::parse_str::<Setting>("level=3").unwrap();
+    let_assert!(Ok(json) = serde_json::to_string(&setting));
+    check!(json == r#""level = 3""#);
+}
@@ -0,0 +1,54 @@
+use assert2::check;
+use assert2::let_assert;
+use proc_macro2::{LineColumn, Span};
+use quote::ToTokens;
+use syn::{Expr, Ident, Token};
+
+use crate::Parse;
+
+#[derive(Parse, crate::ToTokens)]
+enum Statement {
+    Assignment {
+        name: Ident,
+        _eq: Token![=],
+        value: Expr,
+        #[parse(span)]
+        span: Span,
+    },
+    #[parse(parenthesized)]
+    Grouped(Ident, #[parse(span)] Span),
+    #[parse(rename = "pass")]
+    Pass(#[parse(span)] Span),
+}
+
+// helper function to give the start and end of the span as (line, column) pairs
+fn location(span: Span) -> ((usize, usize), (usize, usize)) {
+    let LineColumn { line, column } = span.start();
+    let start = (line, column);
+    let LineColumn { line, column } = span.end();
+    (start, (line, column))
+}
+
+#[test]
+fn span_field_covers_the_whole_variant() {
+    let_assert!(Ok(Statement::Assignment { span, .. }) = syn::parse_str("x = 1 +\n 2"));
+    check!(location(span) == ((1, 0), (2, 2)));
+
+    let_assert!(Ok(Statement::Pass(span)) = syn::parse_str("pass"));
+    check!(location(span) == ((1, 0), (1, 4)));
+}
+
+#[test]
+fn span_field_includes_the_delimiters() {
+    let_assert!(Ok(Statement::Grouped(ident, span)) = syn::parse_str(" ( x )"));
+    check!(ident == "x");
+    check!(location(span) == ((1, 1), (1, 6)));
+}
+
+#[test]
+fn span_field_is_not_emitted() {
+    let statement = syn::parse_str::<Statement>("x = 1").unwrap();
+    check!(statement.to_token_stream().to_string() == "x = 1");
+    let statement = syn::parse_str::<Statement>("(x)").unwrap();
+    check!(statement.to_token_stream().to_string() == "(x)");
+}
@@ -0,0 +1,75 @@
+use assert2::check;
+use assert2::let_assert;
+use quote::ToTokens;
+use syn::{Expr, Ident};
+
+use crate::Parse;
+
+#[derive(Parse, crate::ToTokens)]
+enum Statement {
+    #[parse(syntax = "let $name = $value ;")]
+    Let { name: Ident, value: Expr },
+    #[parse(syntax = "fn $0 ( $($1),* ) $(-> $2)?")]
+    Function(Ident, Vec<Ident>, Option<Ident>),
+    #[parse(syntax = "[ $($0)+ ]")]
+    List(Vec<Ident>),
+    #[parse(syntax = "pass ;")]
+    Pass,
+}
+
+#[test]
+fn literal_tokens_of_template_are_matched() {
+    let statement = syn::parse_str::<Statement>("let x = 1 + y;").unwrap();
+    let_assert!(Statement::Let { name, value } = statement);
+    check!(name == "x");
+    check!(value == syn::parse_str::<Expr>("1 + y").unwrap());
+
+    let statement = syn::parse_str::<Statement>("pass;").unwrap();
+    let_assert!(Statement::Pass = statement);
+
+    check!(syn::parse_str::<Statement>("let x 1;").is_err());
+    check!(syn::parse_str::<Statement>("let x = 1").is_err());
+    check!(syn::parse_str::<Statement>("pass").is_err());
+}
+
+#[test]
+fn repetitions_collect_fields() {
+    let statement = syn::parse_str::<Statement>("fn abide(the, dude) -> rug").unwrap();
+    let_assert!(Statement::Function(name, arguments, Some(returns)) = statement);
+    check!(name == "abide");
+    check!(arguments.len() == 2);
+    check!(arguments[0] == "the");
+    check!(arguments[1] == "dude");
+    check!(returns == "rug");
+
+    let statement = syn::parse_str::<Statement>("fn bowl()").unwrap();
+    let_assert!(Statement::Function(_, arguments, None) = statement);
+    check!(arguments.is_empty());
+
+    let statement = syn::parse_str::<Statement>("[walter donny]").unwrap();
+    let_assert!(Statement::List(items) = statement);
+    check!(items.len() == 2);
+
+    // the `+` repetition must match at least once
+    check!(syn::parse_str::<Statement>("[]").is_err());
+    check!(syn::parse_str::<Statement>("fn abide(the dude)").is_err());
+}
+
+#[test]
+fn templates_are_emitted_as_tokens() {
+    for input in [
+        "let x = 1 + y ;",
+        "fn abide (the , dude) -> rug",
+        "fn bowl ()",
+        "[walter donny]",
+        "pass ;",
+    ] {
+        let statement = syn::parse_str::<Statement>(input).unwrap();
+        check!(
+            statement.to_token_stream().to_string()
+                == syn::parse_str::<proc_macro2::TokenStream>(input)
+                    .unwrap()
+                    .to_string()
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use assert2::check;
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{Expr, Ident, Token};
+
+use crate::Parse;
+
+mod keywords {
+    syn::custom_keyword!(lebowski);
+}
+
+struct TypeId(#[allow(dead_code)] u64);
+
+#[derive(Parse, crate::ToTokens)]
+#[allow(dead_code)]
+enum EnumWithTokens {
+    TwoExpressionsSeparatedByKeyword {
+        first: Expr,
+        _the_dude: keywords::lebowski,
+        second: Expr,
+    },
+    IdentifierPlusPlus(Ident, Token![+], Token![+]),
+    #[parse(ignore)]
+    Resolved(TypeId),
+    #[parse(fallback)]
+    Verbatim(TokenStream),
+}
+
+#[test]
+fn parsed_variants_are_emitted_as_tokens() {
+    for input in [
+        "jeffrey () lebowski el . duderino (& his_dudeness)",
+        "C + +",
+        "+ 1 2 3",
+    ] {
+        let variant = syn::parse_str::<EnumWithTokens>(input).unwrap();
+        check!(variant.to_token_stream().to_string() == input);
+    }
+}
+
+#[test]
+fn emitted_tokens_parse_as_the_same_variant() {
+    let variant = EnumWithTokens::IdentifierPlusPlus(
+        syn::parse_str("C").unwrap(),
+        Default::default(),
+        Default::default(),
+    );
+    let reparsed = syn::parse2::<EnumWithTokens>(variant.to_token_stream()).unwrap();
+    check!(let EnumWithTokens::IdentifierPlusPlus(..) = reparsed);
+}
+
+#[test]
+fn ignored_variants_emit_no_tokens() {
+    let variant = EnumWithTokens::Resolved(TypeId(42));
+    check!(variant.to_token_stream().is_empty());
+}
@@ -0,0 +1,43 @@
+use assert2::check;
+use assert2::let_assert;
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{Expr, Ident, Token};
+
+use crate::Parse;
+
+#[derive(Parse, crate::ToTokens)]
+#[parse(suffix = Token![;])]
+enum Statement {
+    Assignment(Ident, Token![=], Expr, #[parse(tokens)] TokenStream),
+    #[parse(braced)]
+    Block(Ident, #[parse(tokens)] TokenStream),
+}
+
+#[test]
+fn tokens_field_keeps_the_tokens_of_the_variant() {
+    let_assert!(Ok(Statement::Assignment(_, _, _, raw)) = syn::parse_str("x = (1 + 2) * 3;"));
+    check!(raw.to_string() == "x = (1 + 2) * 3");
+
+    let_assert!(Ok(Statement::Block(_, raw)) = syn::parse_str("{ x };"));
+    check!(raw.to_string() == "{ x }");
+}
+
+#[test]
+fn tokens_field_is_emitted_instead_of_the_fields() {
+    let_assert!(Ok(mut statement) = syn::parse_str::<Statement>("x = 1;"));
+    check!(statement.to_token_stream().to_string() == "x = 1 ;");
+
+    // the kept tokens take precedence over the fields
+    if let Statement::Assignment(ref mut ident, ..) = statement {
+        *ident = syn::parse_str("y").unwrap();
+    }
+    check!(statement.to_token_stream().to_string() == "x = 1 ;");
+}
+
+#[test]
+fn tokens_field_keeps_the_spans() {
+    let_assert!(Ok(Statement::Assignment(ident, _, _, raw)) = syn::parse_str("x = 1;"));
+    let_assert!(Some(proc_macro2::TokenTree::Ident(first)) = raw.into_iter().next());
+    check!(first.span().start() == ident.span().start());
+}
@@ -0,0 +1,52 @@
+use assert2::check;
+use std::collections::HashSet;
+use syn::{Ident, LitInt, Token};
+
+use crate::Parse;
+
+#[derive(Parse)]
+#[allow(dead_code)]
+#[parse(kind)]
+enum Setting {
+    Assignment(Ident, Token![=], LitInt),
+    Flag {
+        name: Ident,
+    },
+    #[parse(ignore)]
+    Unset,
+    r#Type(Token![type]),
+}
+
+#[test]
+fn kind_mirrors_the_parsed_variant() {
+    let setting = syn::parse_str::<Setting>("level = 3").unwrap();
+    check!(setting.kind() == SettingKind::Assignment);
+    let setting = syn::parse_str::<Setting>("verbose").unwrap();
+    check!(setting.kind() == SettingKind::Flag);
+    let setting = syn::parse_str::<Setting>("type").unwrap();
+    check!(setting.kind() == SettingKind::r#Type);
+    check!(Setting::Unset.kind() == SettingKind::Unset);
+}
+
+#[test]
+fn variant_names_are_the_declared_names() {
+    let setting = syn::parse_str::<Setting>("level = 3").unwrap();
+    check!(setting.variant_name() == "Assignment");
+    check!(Setting::Unset.variant_name() == "Unset");
+    check!(SettingKind::r#Type.variant_name() == "Type");
+}
+
+#[test]
+fn all_kinds_are_listed_in_order_of_declaration() {
+    check!(
+        SettingKind::ALL
+            == [
+                SettingKind::Assignment,
+                SettingKind::Flag,
+                SettingKind::Unset,
+                SettingKind::r#Type
+            ]
+    );
+    let kinds: HashSet<SettingKind> = SettingKind::ALL.iter().copied().collect();
+    check!(kinds.len() == 4);
+}
@@ -0,0 +1,51 @@
+use assert2::check;
+use assert2::let_assert;
+use syn::{Expr, Ident, LitInt};
+
+use crate::Parse;
+
+#[derive(Parse)]
+enum EnumWithPriorities {
+    Expression(Expr),
+    #[parse(priority = 1)]
+    Identifier(Ident),
+    #[parse(priority = 1)]
+    Integer(LitInt),
+    #[parse(priority = -1)]
+    #[allow(dead_code)]
+    IdentifierAfterExpression(Ident),
+}
+
+#[derive(Parse)]
+enum EnumWithEqualPriorities {
+    #[parse(priority = 2)]
+    First(Ident),
+    #[allow(dead_code)]
+    #[parse(priority = 2)]
+    Second(Ident),
+}
+
+#[test]
+fn variants_with_higher_priority_are_tried_first() {
+    let variant = syn::parse_str::<EnumWithPriorities>("the_dude").unwrap();
+    let_assert!(EnumWithPriorities::Identifier(ident) = variant);
+    check!(ident == syn::parse_str::<Ident>("the_dude").unwrap());
+
+    let variant = syn::parse_str::<EnumWithPriorities>("1337").unwrap();
+    let_assert!(EnumWithPriorities::Integer(int) = variant);
+    check!(int == syn::parse_str::<LitInt>("1337").unwrap());
+}
+
+#[test]
+fn variants_with_default_priority_are_tried_after_higher_priorities() {
+    let variant = syn::parse_str::<EnumWithPriorities>("(the_dude + 1)").unwrap();
+    let_assert!(EnumWithPriorities::Expression(expression) = variant);
+    check!(expression == syn::parse_str::<Expr>("(the_dude + 1)").unwrap());
+}
+
+#[test]
+fn variants_with_equal_priority_are_tried_in_order_of_declaration() {
+    let variant = syn::parse_str::<EnumWithEqualPriorities>("walter").unwrap();
+    let_assert!(EnumWithEqualPriorities::First(ident) = variant);
+    check!(ident == syn::parse_str::<Ident>("walter").unwrap());
+}
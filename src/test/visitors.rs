@@ -0,0 +1,146 @@
+use assert2::check;
+use assert2::let_assert;
+use syn::Ident;
+
+use ast::{Condition, Fold, Loop, Program, Statement, Visit, VisitMut};
+
+#[crate::visitors]
+mod ast {
+    use syn::{Expr, Ident, Token};
+
+    use crate::Parse;
+
+    pub struct Program {
+        pub statements: Vec<Statement>,
+    }
+
+    // the boxed containers are needlessly boxed on purpose, to test the traversal of boxes
+    #[allow(clippy::box_collection)]
+    pub struct Loop {
+        pub body: Box<Vec<Statement>>,
+        pub condition: Box<Option<Condition>>,
+    }
+
+    #[derive(Parse)]
+    pub enum Statement {
+        #[parse(syntax = "{ $($0)* }")]
+        Block(Vec<Statement>),
+        #[parse(syntax = "let $name = $value ;")]
+        Let { name: Ident, value: Expr },
+        #[parse(syntax = "if $0 $1 $(else $2)?")]
+        If(Condition, Box<Statement>, Option<Box<Statement>>),
+    }
+
+    #[derive(Parse)]
+    pub enum Condition {
+        Not(Token![!], Box<Condition>),
+        Variable(Ident),
+    }
+}
+
+fn parse_program(source: &str) -> Program {
+    let_assert!(Ok(Statement::Block(statements)) = syn::parse_str(&format!("{{ {} }}", source)));
+    Program { statements }
+}
+
+// collects the names of the let statements and the variables of the conditions in order
+#[derive(Default)]
+struct Names(Vec<String>);
+
+impl<'ast> Visit<'ast> for Names {
+    fn visit_statement(&mut self, node: &'ast Statement) {
+        if let Statement::Let { name, .. } = node {
+            self.0.push(name.to_string());
+        }
+        ast::visit_statement(self, node);
+    }
+
+    fn visit_condition(&mut self, node: &'ast Condition) {
+        if let Condition::Variable(variable) = node {
+            self.0.push(variable.to_string());
+        }
+        ast::visit_condition(self, node);
+    }
+}
+
+#[test]
+fn visit_recurses_into_the_types_of_the_module() {
+    let program = parse_program("let x = 1; if !a { let y = 2; } else if b let z = 3;");
+    let mut names = Names::default();
+    names.visit_program(&program);
+    check!(names.0 == ["x", "a", "y", "b", "z"]);
+}
+
+#[test]
+fn visit_mut_changes_the_nodes_in_place() {
+    struct Rename;
+    impl VisitMut for Rename {
+        fn visit_condition_mut(&mut self, node: &mut Condition) {
+            if let Condition::Variable(variable) = node {
+                *variable = Ident::new(&format!("{}_renamed", variable), variable.span());
+            }
+            ast::visit_condition_mut(self, node);
+        }
+    }
+
+    let mut program = parse_program("if !!a let x = 1;");
+    Rename.visit_program_mut(&mut program);
+    let mut names = Names::default();
+    names.visit_program(&program);
+    check!(names.0 == ["a_renamed", "x"]);
+}
+
+#[test]
+fn fold_builds_new_nodes() {
+    // removes double negations from the conditions
+    struct Simplify;
+    impl Fold for Simplify {
+        fn fold_condition(&mut self, node: Condition) -> Condition {
+            match ast::fold_condition(self, node) {
+                Condition::Not(not, inner) => match *inner {
+                    Condition::Not(_, inner) => *inner,
+                    inner => Condition::Not(not, Box::new(inner)),
+                },
+                variable => variable,
+            }
+        }
+    }
+
+    let program = Simplify.fold_program(parse_program("if !!!!a {} if !!!b {}"));
+    let_assert!([Statement::If(first, ..), Statement::If(second, ..)] = &program.statements[..]);
+    let_assert!(Condition::Variable(_) = first);
+    let_assert!(Condition::Not(_, inner) = second);
+    let_assert!(Condition::Variable(variable) = &**inner);
+    check!(variable == "b");
+}
+
+#[test]
+fn boxed_containers_are_traversed() {
+    // removes all statements but the first from the body and negates the condition
+    struct Shorten;
+    impl Fold for Shorten {
+        fn fold_loop(&mut self, node: Loop) -> Loop {
+            let mut node = ast::fold_loop(self, node);
+            node.body.truncate(1);
+            node
+        }
+
+        fn fold_condition(&mut self, node: Condition) -> Condition {
+            Condition::Not(Default::default(), Box::new(node))
+        }
+    }
+
+    let program = parse_program("let x = 1; let y = 2;");
+    let condition = syn::parse_str::<Condition>("a").unwrap();
+    let loop_ = Loop {
+        body: Box::new(program.statements),
+        condition: Box::new(Some(condition)),
+    };
+    let mut names = Names::default();
+    names.visit_loop(&loop_);
+    check!(names.0 == ["x", "y", "a"]);
+
+    let loop_ = Shorten.fold_loop(loop_);
+    check!(loop_.body.len() == 1);
+    let_assert!(Some(Condition::Not(..)) = &*loop_.condition);
+}
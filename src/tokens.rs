@@ -0,0 +1,31 @@
+// helpers for the code that is generated for fields that keep the tokens or the span a variant was parsed from
+use proc_macro2::{Span, TokenTree};
+use syn::buffer::Cursor;
+
+// collect the token trees from the start cursor up to (but excluding) the end cursor. The end
+// cursor must come after the start cursor and must be on the same level of nesting.
+pub fn tokens_between<T: FromIterator<TokenTree>>(start: Cursor, end: Cursor) -> T {
+    let mut tokens = Vec::new();
+    let mut cursor = start;
+    while cursor != end {
+        match cursor.token_tree() {
+            Some((token_tree, next)) => {
+                tokens.push(token_tree);
+                cursor = next;
+            }
+            None => break,
+        }
+    }
+    tokens.into_iter().collect()
+}
+
+// the span from the first token tree at the start cursor to the last token tree before the end
+// cursor. If the spans cannot be joined, the span of the first token tree is returned. If there
+// are no token trees between the cursors, the span of the start cursor is returned.
+pub fn span_between(start: Cursor, end: Cursor) -> Span {
+    let tokens: Vec<TokenTree> = tokens_between(start, end);
+    match (tokens.first(), tokens.last()) {
+        (Some(first), Some(last)) => first.span().join(last.span()).unwrap_or(first.span()),
+        _ => start.span(),
+    }
+}
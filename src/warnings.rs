@@ -0,0 +1,97 @@
+// the side channel for warnings that are recorded while parsing, e.g. when a variant
+// marked `#[parse(deprecated = "...")]` was parsed
+use std::cell::RefCell;
+use std::fmt;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned, ToTokens};
+
+/// The maximum number of warnings that are kept per thread. Further warnings are dropped until
+/// the warnings are taken with [`warnings`], so that the warnings of code that never takes them
+/// cannot grow without bound.
+pub const MAX_WARNINGS: usize = 1024;
+
+thread_local! {
+    static WARNINGS: RefCell<Vec<Warning>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A warning that was recorded while parsing, e.g. because a variant marked
+/// `#[parse(deprecated = "...")]` was parsed. See [`warnings`].
+#[derive(Clone, Debug)]
+pub struct Warning {
+    message: String,
+    span: Span,
+}
+
+impl Warning {
+    /// The message of the warning, e.g. the note given by `#[parse(deprecated = "...")]`.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The span of the tokens that caused the warning.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Emits the warning as a use of a deprecated item, so that the compiler reports
+/// the message as a deprecation warning at the span of the warning when the tokens
+/// are part of the output of a procedural macro.
+impl ToTokens for Warning {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let message = &self.message;
+        let deprecated_use = quote_spanned! {self.span=> parse_variants_warning};
+        tokens.extend(quote! {
+            const _: () = {
+                #[deprecated(note = #message)]
+                #[allow(non_camel_case_types)]
+                struct parse_variants_warning;
+                let _ = #deprecated_use;
+            };
+        });
+    }
+}
+
+/// Take all warnings that were recorded on the current thread while parsing since the last call
+/// to this function. A warning is recorded when a variant marked `#[parse(deprecated = "...")]` is
+/// parsed successfully. The warnings of the parse attempts that the derived parse functions discard,
+/// e.g. because the variant that contains the deprecated variant fails later, are discarded with them.
+/// Speculative parsing outside of the derived parse functions, e.g. with a fork of a `ParseStream`,
+/// cannot discard the warnings of the values that it drops.
+///
+/// The warnings are buffered until they are taken, so code that parses repeatedly, like a language
+/// server or a procedural macro that is invoked many times on the same thread, should take them
+/// regularly. At most [`MAX_WARNINGS`] warnings are buffered and later warnings are dropped.
+pub fn warnings() -> Vec<Warning> {
+    WARNINGS.with(|warnings| std::mem::take(&mut *warnings.borrow_mut()))
+}
+
+// record a warning for the current thread, unless the buffer is full
+pub fn record_warning(span: Span, message: &str) {
+    WARNINGS.with(|warnings| {
+        let mut warnings = warnings.borrow_mut();
+        if warnings.len() < MAX_WARNINGS {
+            warnings.push(Warning {
+                message: message.to_string(),
+                span,
+            })
+        }
+    });
+}
+
+// the number of warnings recorded on the current thread, which marks the start of a parse attempt
+pub fn warnings_mark() -> usize {
+    WARNINGS.with(|warnings| warnings.borrow().len())
+}
+
+// discard the warnings that were recorded since the mark, because the parse attempt failed
+pub fn discard_warnings(mark: usize) {
+    WARNINGS.with(|warnings| warnings.borrow_mut().truncate(mark));
+}